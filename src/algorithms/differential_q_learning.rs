@@ -0,0 +1,85 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult, CHECKPOINT_STEPS};
+use crate::algorithms::epsilon_greedy::{epsilon_greedy_action, greedy_action};
+use rand::Rng;
+
+// Q-Learning différentiel (critère de récompense moyenne), sur un seul flux de `num_steps` pas :
+//   δ = r - rho + max_a' Q(s', a') - Q(s, a),   Q(s, a) += α δ,   rho += η α δ.
+// Prévu pour une tâche continue (cf. `ContinuingTask`) ; un environnement épisodique est relancé par `reset`.
+// Renvoie la politique greedy et les valeurs d'action différentielles, avec l'estimation de rho dans `average_reward`.
+pub fn differential_q_learning(
+    env: &mut impl Environment,
+    num_steps: usize,
+    learning_rate: f32,
+    average_reward_step_size: f32,
+    epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut rho = 0.0f32;
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for step in 1..=num_steps {
+        if env.is_game_over() {
+            recorder.end_episode(env.score(), &q_values);
+            env.reset();
+        }
+        let s = env.state_id();
+        let available_actions = env.available_actions();
+        let a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+        recorder.record_step(s, &available_actions);
+        let Transition { reward: r, next_state: s_p, terminated, .. } = env.step(a);
+
+        let q_s_p = if terminated {
+            0.0
+        } else {
+            q_values[s_p][greedy_action(&q_values[s_p], &env.available_actions())]
+        };
+        let delta = r - rho + q_s_p - q_values[s][a];
+        q_values[s][a] += learning_rate * delta;
+        rho += average_reward_step_size * learning_rate * delta;
+        if step % CHECKPOINT_STEPS == 0 {
+            recorder.checkpoint(&q_values);
+        }
+    }
+
+    LearningResult {
+        average_reward: Some(rho),
+        ..recorder.finish(q_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::continuing_task::ContinuingTask;
+    use crate::envs::line_world::LineWorld;
+    use crate::envs::map_grid_world::MapGridWorld;
+
+    #[test]
+    fn average_reward_converges_on_continuing_line_world() {
+        // Deux pas vers la droite rapportent +1 puis on repart du centre : rho optimal = 1 / 2
+        let result = differential_q_learning(&mut ContinuingTask::new(LineWorld::<5>::new()), 100_000, 0.1, 0.1, 0.1, &mut StdRng::seed_from_u64(0));
+        let rho = result.average_reward.unwrap();
+        assert!((rho - 0.5).abs() < 0.02, "rho = {}", rho);
+        assert_eq!(result.pi[1..4], [1, 1, 1]);
+    }
+
+    #[test]
+    fn average_reward_converges_on_continuing_map_grid_world() {
+        // Le plus court chemin de S à G fait 7 pas : rho optimal = 1 / 7
+        let map_grid_world = MapGridWorld::from_map("
+            S..#.
+            .#...
+            ...#X
+            .#..G
+        ").unwrap();
+        let result = differential_q_learning(&mut ContinuingTask::new(map_grid_world), 200_000, 0.1, 0.1, 0.1, &mut StdRng::seed_from_u64(0));
+        let rho = result.average_reward.unwrap();
+        assert!((rho - 1.0 / 7.0).abs() < 0.02, "rho = {}", rho);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::{epsilon_greedy_action, greedy_action};
+use rand::Rng;
+
+// Double Q-Learning : une des deux tables choisit l'action greedy en s', l'autre l'évalue.
+// Le comportement est epsilon-greedy sur Q1 + Q2 ; la table renvoyée est la moyenne des deux.
+pub fn double_q_learning(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q1_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut q2_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        while !env.is_game_over() {
+            let s = env.state_id();
+            let q_sum = q1_values[s].iter().zip(q2_values[s].iter()).map(|(q1, q2)| q1 + q2).collect::<Vec<f32>>();
+            let available_actions = env.available_actions();
+            let a = epsilon_greedy_action(&q_sum, &available_actions, epsilon, rng);
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, terminated, .. } = env.step(a);
+
+            let (q_updated, q_evaluator) = if rng.gen::<bool>() {
+                (&mut q1_values, &q2_values)
+            } else {
+                (&mut q2_values, &q1_values)
+            };
+            let q_s_p = if terminated {
+                0.0
+            } else {
+                q_evaluator[s_p][greedy_action(&q_updated[s_p], &env.available_actions())]
+            };
+            q_updated[s][a] += learning_rate * (r + gamma * q_s_p - q_updated[s][a]);
+        }
+        recorder.end_episode(env.score(), &mean_q_values(&q1_values, &q2_values));
+    }
+
+    recorder.finish(mean_q_values(&q1_values, &q2_values))
+}
+
+fn mean_q_values(q1_values: &[Vec<f32>], q2_values: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    q1_values.iter().zip(q2_values.iter())
+        .map(|(q1_s, q2_s)| q1_s.iter().zip(q2_s.iter()).map(|(q1, q2)| (q1 + q2) / 2.0).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::algorithms::q_learning::q_learning;
+    use crate::benchmarks::maximization_bias::maximization_bias;
+    use crate::envs::maximization_bias::MaximizationBias;
+
+    // Le bruit de l'environnement est dérivé de la même graine que l'apprentissage
+    fn run(seed: u64) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut env = MaximizationBias::<10>::from_rng(&mut rng);
+        let q_values = double_q_learning(&mut env, 300, 0.1, 1.0, 0.1, &mut rng).q_values;
+        q_values.iter().flatten().map(|q| q.to_bits()).collect()
+    }
+
+    #[test]
+    fn same_seed_gives_bit_identical_q_values() {
+        assert_eq!(run(7), run(7));
+    }
+
+    #[test]
+    fn different_seeds_give_different_q_values() {
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn double_q_learning_overestimates_going_left_less_than_q_learning() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (q_learning_left, _, q_learning_left_ratio) = maximization_bias::<10>(
+            || {
+                let mut env = MaximizationBias::<10>::from_rng(&mut rng);
+                q_learning(&mut env, 50, 0.1, 1.0, 0.1, &mut rng).q_values
+            },
+            200,
+        );
+        let (double_left, _, double_left_ratio) = maximization_bias::<10>(
+            || {
+                let mut env = MaximizationBias::<10>::from_rng(&mut rng);
+                double_q_learning(&mut env, 50, 0.1, 1.0, 0.1, &mut rng).q_values
+            },
+            200,
+        );
+        assert!(double_left < q_learning_left, "Q(A, left): double {} vs Q-Learning {}", double_left, q_learning_left);
+        assert!(double_left_ratio < q_learning_left_ratio, "left from A: double {} vs Q-Learning {}", double_left_ratio, q_learning_left_ratio);
+    }
+}
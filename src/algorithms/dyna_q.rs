@@ -0,0 +1,98 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::epsilon_greedy_action;
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DynaVariant {
+    DynaQ,
+    // Bonus d'exploration kappa * sqrt(tau), tau étant le nombre de pas depuis le dernier essai réel de (s, a)
+    DynaQPlus { kappa: f32 },
+}
+
+// Dyna-Q : chaque pas réel est suivi de `planning_steps` mises à jour simulées
+// tirées d'un modèle déterministe appris (s, a) -> (r, s').
+#[allow(clippy::too_many_arguments)]
+pub fn dyna_q(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    planning_steps: usize,
+    variant: DynaVariant,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut model: Vec<Vec<Option<(f32, usize)>>> = vec![vec![None; env.num_actions()]; env.num_states()];
+    let mut last_visit = vec![vec![0usize; env.num_actions()]; env.num_states()];
+    let mut observed_pairs = vec![];
+    let mut time_step = 0usize;
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        while !env.is_game_over() {
+            let s = env.state_id();
+            let available_actions = env.available_actions();
+
+            // Dyna-Q+ : les actions jamais essayées depuis un état connu sont modélisées comme
+            // ramenant au même état avec une récompense nulle, pour que le bonus puisse les attirer
+            if let DynaVariant::DynaQPlus { .. } = variant {
+                for &a in available_actions.iter() {
+                    if model[s][a].is_none() {
+                        model[s][a] = Some((0.0, s));
+                        observed_pairs.push((s, a));
+                    }
+                }
+            }
+
+            let a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, .. } = env.step(a);
+            time_step += 1;
+
+            let q_s_p = q_values[s_p].iter().max_by(|q1, q2| q1.partial_cmp(q2).unwrap()).unwrap();
+            q_values[s][a] += learning_rate * (r + gamma * q_s_p - q_values[s][a]);
+
+            if model[s][a].is_none() {
+                observed_pairs.push((s, a));
+            }
+            model[s][a] = Some((r, s_p));
+            last_visit[s][a] = time_step;
+
+            for _ in 0..planning_steps {
+                let &(s, a) = observed_pairs.choose(rng).unwrap();
+                let (mut r, s_p) = model[s][a].unwrap();
+                if let DynaVariant::DynaQPlus { kappa } = variant {
+                    r += kappa * ((time_step - last_visit[s][a]) as f32).sqrt();
+                }
+                let q_s_p = q_values[s_p].iter().max_by(|q1, q2| q1.partial_cmp(q2).unwrap()).unwrap();
+                q_values[s][a] += learning_rate * (r + gamma * q_s_p - q_values[s][a]);
+            }
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn planning_learns_line_world() {
+        for variant in [DynaVariant::DynaQ, DynaVariant::DynaQPlus { kappa: 0.001 }] {
+            let result = dyna_q(&mut LineWorld::<5>::new(), 200, 0.5, 0.9, 0.1, 50, variant, &mut StdRng::seed_from_u64(0));
+            assert_eq!(result.pi[1..4], [1, 1, 1], "{:?}", variant);
+            assert!((result.q_values[2][1] - 0.9).abs() < 0.05, "{:?}: Q(2, right) = {}", variant, result.q_values[2][1]);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceKind {
+    Accumulating,
+    Replacing,
+    // Trace "dutch" tabulaire : e(s, a) <- (1 - alpha) e(s, a) + 1
+    Dutch,
+}
+
+// Traces d'éligibilité tabulaires sur Q. Seules les paires de trace non nulle sont parcourues,
+// ce qui garde le coût d'un pas proportionnel au nombre de paires récemment visitées.
+pub struct EligibilityTraces {
+    kind: TraceKind,
+    traces: Vec<Vec<f32>>,
+    active_pairs: Vec<(usize, usize)>,
+}
+
+impl EligibilityTraces {
+    pub fn new(kind: TraceKind, num_states: usize, num_actions: usize) -> Self {
+        EligibilityTraces {
+            kind,
+            traces: vec![vec![0.0; num_actions]; num_states],
+            active_pairs: vec![],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for &(s, a) in self.active_pairs.iter() {
+            self.traces[s][a] = 0.0;
+        }
+        self.active_pairs.clear();
+    }
+
+    pub fn visit(&mut self, s: usize, a: usize, learning_rate: f32) {
+        let trace = self.traces[s][a];
+        if trace == 0.0 {
+            self.active_pairs.push((s, a));
+        }
+        self.traces[s][a] = match self.kind {
+            TraceKind::Accumulating => trace + 1.0,
+            TraceKind::Replacing => 1.0,
+            TraceKind::Dutch => (1.0 - learning_rate) * trace + 1.0,
+        };
+    }
+
+    // Q(s, a) += alpha * delta * e(s, a) pour toutes les paires actives
+    pub fn update(&self, q_values: &mut [Vec<f32>], learning_rate: f32, delta: f32) {
+        for &(s, a) in self.active_pairs.iter() {
+            q_values[s][a] += learning_rate * delta * self.traces[s][a];
+        }
+    }
+
+    // Multiplie toutes les traces par `factor` et oublie celles devenues négligeables
+    pub fn decay(&mut self, factor: f32) {
+        let traces = &mut self.traces;
+        self.active_pairs.retain(|&(s, a)| {
+            traces[s][a] *= factor;
+            if traces[s][a].abs() < 1e-6 {
+                traces[s][a] = 0.0;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+use std::time::Instant;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+use crate::algorithms::training_result::PlanningResult;
+
+const TERMINATION_TOLERANCE: f32 = 1e-6;
+
+// Politique impropre : depuis `improper_states`, suivre `pi` ne termine jamais avec probabilité 1
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImproperPolicyError {
+    pub pi: Vec<usize>,
+    pub value_function: Vec<f32>,
+    pub improper_states: Vec<usize>,
+    pub sweeps: usize,
+}
+
+impl fmt::Display for ImproperPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Greedy policy never terminates from states {:?} (after {} sweeps)", self.improper_states, self.sweeps)
+    }
+}
+
+impl std::error::Error for ImproperPolicyError {}
+
+pub fn episodic_value_iteration(mdp: &impl Mdp, theta: f32, max_sweeps: usize) -> Result<PlanningResult, ImproperPolicyError> {
+    let model = SparseTransitionModel::from_mdp(mdp);
+    episodic_value_iteration_with_model(&model, theta, max_sweeps)
+}
+
+// Value iteration sans actualisation (gamma = 1) pour les tâches épisodiques.
+// La convergence n'est garantie que s'il existe une politique propre optimale : on s'arrête donc après `max_sweeps`
+// balayages au plus, et on renvoie une erreur listant les états d'où la politique greedy ne termine pas.
+pub fn episodic_value_iteration_with_model(
+    model: &SparseTransitionModel,
+    theta: f32,
+    max_sweeps: usize,
+) -> Result<PlanningResult, ImproperPolicyError> {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let mut value_function = vec![0.0; num_states];
+
+    let start_time = Instant::now();
+
+    let mut sweeps = 0;
+    let mut converged = false;
+    while sweeps < max_sweeps {
+        let mut delta = 0.0f32;
+        for s in 0..num_states {
+            let v = value_function[s];
+            value_function[s] = (0..num_actions)
+                .map(|a| model.q_value(s, a, &value_function, 1.0))
+                .fold(f32::NEG_INFINITY, f32::max);
+            delta = delta.max((v - value_function[s]).abs());
+        }
+        sweeps += 1;
+        if delta < theta {
+            converged = true;
+            break;
+        }
+    }
+
+    let (pi, improper_states) = proper_greedy_policy(model, &value_function, theta);
+    if converged && improper_states.is_empty() {
+        Ok(PlanningResult {
+            pi,
+            value_function,
+            duration: start_time.elapsed(),
+            iterations: sweeps,
+            converged,
+            average_reward: None,
+            policy_iteration_stats: None,
+        })
+    } else {
+        Err(ImproperPolicyError {
+            pi,
+            value_function,
+            improper_states,
+            sweeps,
+        })
+    }
+}
+
+// Politique greedy par rapport à V qui termine si possible. Avec gamma = 1, des cycles de récompense nulle
+// donnent des actions ex aequo dont certaines ne terminent jamais : parmi les actions à moins de `tolerance`
+// de la meilleure, on choisit celle qui rapproche d'un état d'où l'on sait déjà terminer.
+// Avec la convention des MDPEnv du dépôt, l'épisode s'arrête quand la masse de probabilité "sort" du modèle :
+// une paire (s, a) dont les probabilités somment à moins de 1 (en particulier un état terminal, sans transition).
+// Renvoie la politique et les états d'où aucune action quasi optimale ne termine.
+pub fn proper_greedy_policy(model: &SparseTransitionModel, value_function: &[f32], tolerance: f32) -> (Vec<usize>, Vec<usize>) {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let near_optimal_actions = (0..num_states)
+        .map(|s| {
+            let q_s = (0..num_actions).map(|a| model.q_value(s, a, value_function, 1.0)).collect::<Vec<f32>>();
+            let best = q_s.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            (0..num_actions).filter(|&a| q_s[a] >= best - tolerance).collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
+
+    let mut pi = near_optimal_actions.iter().map(|actions| actions[0]).collect::<Vec<usize>>();
+    let mut proper = vec![false; num_states];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for s in 0..num_states {
+            if proper[s] {
+                continue;
+            }
+            let terminating_action = near_optimal_actions[s].iter().find(|&&a| {
+                let transitions = model.transitions(s, a);
+                let total = transitions.iter().fold(0.0, |total, &(_, _, probability)| total + probability);
+                total < 1.0 - TERMINATION_TOLERANCE
+                    || transitions.iter().any(|&(s_p, _, probability)| probability > 0.0 && proper[s_p])
+            });
+            if let Some(&a) = terminating_action {
+                pi[s] = a;
+                proper[s] = true;
+                changed = true;
+            }
+        }
+    }
+    (pi, (0..num_states).filter(|&s| !proper[s]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positive_reward_cycle_is_improper() {
+        // 0 <-> 1 avec +1 à chaque passage (action 0), l'action 1 termine sans récompense : V diverge
+        let model = SparseTransitionModel::from_parts(2, 2, vec![1.0], vec![0, 1, 1, 2, 2], vec![(1, 0, 1.0), (0, 0, 1.0)]);
+        let error = episodic_value_iteration_with_model(&model, 0.0001, 100).unwrap_err();
+        assert_eq!(error.sweeps, 100);
+        assert_eq!(error.improper_states, vec![0, 1]);
+        assert_eq!(error.pi, vec![0, 0]);
+    }
+
+    #[test]
+    fn zero_reward_tie_chooses_the_terminating_action() {
+        // Depuis 0, l'action 0 boucle sur place et l'action 1 mène à l'état terminal 1, toutes deux sans récompense :
+        // les deux actions valent 0, seule la seconde termine
+        let model = SparseTransitionModel::from_parts(2, 2, vec![0.0], vec![0, 1, 2, 2, 2], vec![(0, 0, 1.0), (1, 0, 1.0)]);
+        let result = episodic_value_iteration_with_model(&model, 0.0001, 100).unwrap();
+        assert_eq!(result.value_function, vec![0.0, 0.0]);
+        assert_eq!(result.pi[0], 1);
+    }
+}
@@ -0,0 +1,28 @@
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+pub fn greedy_action(q_s: &[f32], available_actions: &[usize]) -> usize {
+    *available_actions.iter().max_by(|a1, a2| q_s[**a1].partial_cmp(&q_s[**a2]).unwrap()).unwrap()
+}
+
+pub fn epsilon_greedy_action(q_s: &[f32], available_actions: &[usize], epsilon: f32, rng: &mut impl Rng) -> usize {
+    if rng.gen::<f32>() < epsilon {
+        *available_actions.choose(rng).unwrap()
+    } else {
+        greedy_action(q_s, available_actions)
+    }
+}
+
+// Probabilités π(a|s) d'une politique epsilon-greedy, restreinte aux actions disponibles
+pub fn epsilon_greedy_probabilities(q_s: &[f32], available_actions: &[usize], epsilon: f32) -> Vec<f32> {
+    let mut probabilities = vec![0.0; q_s.len()];
+    if available_actions.is_empty() {
+        return probabilities;
+    }
+    let exploration = epsilon / available_actions.len() as f32;
+    for &a in available_actions {
+        probabilities[a] = exploration;
+    }
+    probabilities[greedy_action(q_s, available_actions)] += 1.0 - epsilon;
+    probabilities
+}
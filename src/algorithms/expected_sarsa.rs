@@ -0,0 +1,72 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::{epsilon_greedy_action, epsilon_greedy_probabilities};
+use rand::Rng;
+
+// `epsilon` règle la politique de comportement, `target_epsilon` la politique cible
+// sur laquelle on prend l'espérance (target_epsilon = 0 donne Q-Learning, target_epsilon = epsilon donne la version on-policy)
+pub fn expected_sarsa(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    target_epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        while !env.is_game_over() {
+            let s = env.state_id();
+            let available_actions = env.available_actions();
+            let a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, terminated, .. } = env.step(a);
+
+            let expected_q_s_p = if terminated {
+                0.0
+            } else {
+                let available_actions = env.available_actions();
+                epsilon_greedy_probabilities(&q_values[s_p], &available_actions, target_epsilon)
+                    .iter()
+                    .zip(q_values[s_p].iter())
+                    .map(|(p, q)| p * q)
+                    .sum::<f32>()
+            };
+            q_values[s][a] += learning_rate * (r + gamma * expected_q_s_p - q_values[s][a]);
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn learns_to_move_right_on_line_world() {
+        // On-policy (target_epsilon = epsilon) et Q-Learning (target_epsilon = 0)
+        for target_epsilon in [0.1, 0.0] {
+            let result = expected_sarsa(&mut LineWorld::<5>::new(), 500, 0.1, 0.9, 0.1, target_epsilon, &mut StdRng::seed_from_u64(0));
+            assert_eq!(result.pi[1..4], [1, 1, 1]);
+            assert!(result.q_values[3][1] > 0.9);
+        }
+    }
+
+    #[test]
+    fn greedy_target_estimates_optimal_values() {
+        // Avec une cible greedy, Q(2, droite) tend vers gamma * 1 quelle que soit l'exploration
+        let result = expected_sarsa(&mut LineWorld::<5>::new(), 2_000, 0.1, 0.9, 0.5, 0.0, &mut StdRng::seed_from_u64(0));
+        assert!((result.q_values[2][1] - 0.9).abs() < 1e-3);
+    }
+}
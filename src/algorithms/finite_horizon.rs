@@ -0,0 +1,64 @@
+use std::time::Instant;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+use crate::algorithms::training_result::FiniteHorizonResult;
+
+pub fn finite_horizon(mdp: &impl Mdp, horizon: usize, gamma: f32) -> FiniteHorizonResult {
+    let model = SparseTransitionModel::from_mdp(mdp);
+    finite_horizon_with_model(&model, horizon, gamma)
+}
+
+// Induction arrière sur un horizon fini H : pi[t][s] et V[t][s] pour t = 0..H,
+// où V[t][s] est la valeur optimale depuis s quand il reste H - t pas (V[H] = 0).
+pub fn finite_horizon_with_model(model: &SparseTransitionModel, horizon: usize, gamma: f32) -> FiniteHorizonResult {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let mut pi = vec![vec![0usize; num_states]; horizon];
+    let mut value_function = vec![vec![0.0; num_states]; horizon];
+
+    let start_time = Instant::now();
+
+    let mut next_values = vec![0.0; num_states];
+    for t in (0..horizon).rev() {
+        for s in 0..num_states {
+            // En cas d'égalité on garde la première action, comme `value_iteration`
+            let (best_action, best_value) = (0..num_actions)
+                .map(|a| (a, model.q_value(s, a, &next_values, gamma)))
+                .fold((0, f32::NEG_INFINITY), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+            pi[t][s] = best_action;
+            value_function[t][s] = best_value;
+        }
+        next_values.copy_from_slice(&value_function[t]);
+    }
+
+    FiniteHorizonResult {
+        pi,
+        value_function,
+        duration: start_time.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_step_backward_induction() {
+        // État 0 : action 0 reste en 0 (+1), action 1 va en 1 (0). État 1 : action 0 va en 2 terminal (+5), action 1 reste en 1 (0).
+        let model = SparseTransitionModel::from_parts(
+            3,
+            2,
+            vec![0.0, 1.0, 5.0],
+            vec![0, 1, 2, 3, 4, 4, 4],
+            vec![(0, 1, 1.0), (1, 0, 1.0), (2, 2, 1.0), (1, 0, 1.0)],
+        );
+        let FiniteHorizonResult { pi, value_function, .. } = finite_horizon_with_model(&model, 2, 0.5);
+
+        // t = 1, un pas restant : le +1 immédiat l'emporte en 0, le +5 en 1
+        assert_eq!(value_function[1], vec![1.0, 5.0, 0.0]);
+        assert_eq!(pi[1], vec![0, 0, 0]);
+        // t = 0, deux pas restants : en 0, max(1 + 0.5 * 1, 0 + 0.5 * 5) = 2.5 ; en 1, max(5, 0 + 0.5 * 5) = 5
+        assert_eq!(value_function[0], vec![2.5, 5.0, 0.0]);
+        assert_eq!(pi[0], vec![1, 0, 0]);
+    }
+}
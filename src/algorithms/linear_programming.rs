@@ -0,0 +1,258 @@
+use std::fmt;
+use std::time::Instant;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+use crate::algorithms::training_result::PlanningResult;
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinearProgramError {
+    Infeasible,
+    Unbounded,
+    // Le système (I - gamma P_π) V = r_π de la politique trouvée n'a pas de solution unique
+    Singular,
+}
+
+impl fmt::Display for LinearProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinearProgramError::Infeasible => write!(f, "Linear program is infeasible"),
+            LinearProgramError::Unbounded => write!(f, "Linear program is unbounded"),
+            LinearProgramError::Singular => write!(f, "Policy evaluation system is singular"),
+        }
+    }
+}
+
+impl std::error::Error for LinearProgramError {}
+
+pub fn linear_programming(mdp: &impl Mdp, gamma: f32) -> Result<PlanningResult, LinearProgramError> {
+    let model = SparseTransitionModel::from_mdp(mdp);
+    linear_programming_with_model(&model, gamma)
+}
+
+// Résolution exacte des équations d'optimalité de Bellman par programmation linéaire, via le programme dual :
+//   max  sum_{s,a} x(s,a) r(s,a)
+//   t.q. sum_a x(s',a) - gamma * sum_{s,a} p(s'|s,a) x(s,a) = 1   pour tout s',   x >= 0
+// x(s,a) est la fréquence d'occupation actualisée de (s, a) : la politique optimale choisit en chaque état l'action
+// de fréquence maximale, et V est ensuite obtenue exactement en résolvant (I - gamma P_π) V = r_π.
+// Comme `value_iteration`, une action sans transition vaut 0 (c'est ainsi que les MDPEnv du dépôt codent les états terminaux).
+// Renvoie une erreur si le programme n'a pas d'optimum, ce qui arrive avec gamma = 1 ou un modèle dont les probabilités
+// somment à plus de 1, ou si l'évaluation de la politique trouvée est singulière.
+pub fn linear_programming_with_model(model: &SparseTransitionModel, gamma: f32) -> Result<PlanningResult, LinearProgramError> {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let gamma = gamma as f64;
+
+    let start_time = Instant::now();
+
+    let expected_rewards = (0..num_states)
+        .flat_map(|s| (0..num_actions).map(move |a| (s, a)))
+        .map(|(s, a)| model.transitions(s, a).iter().fold(0.0, |total, &(_, r_index, p)| total + p as f64 * model.rewards()[r_index] as f64))
+        .collect::<Vec<f64>>();
+
+    let mut constraints = vec![vec![0.0; num_states * num_actions]; num_states];
+    for s in 0..num_states {
+        for a in 0..num_actions {
+            let column = s * num_actions + a;
+            constraints[s][column] += 1.0;
+            for &(s_p, _, p) in model.transitions(s, a) {
+                constraints[s_p][column] -= gamma * p as f64;
+            }
+        }
+    }
+
+    let occupancies = simplex_maximize(constraints, vec![1.0; num_states], expected_rewards.clone())?;
+
+    let pi = (0..num_states)
+        .map(|s| (0..num_actions).max_by(|a1, a2| {
+            occupancies[s * num_actions + a1].partial_cmp(&occupancies[s * num_actions + a2]).unwrap().then(a2.cmp(a1))
+        }).unwrap())
+        .collect::<Vec<usize>>();
+
+    // (I - gamma P_π) V = r_π
+    let mut system = vec![vec![0.0; num_states]; num_states];
+    let mut rhs = vec![0.0; num_states];
+    for s in 0..num_states {
+        system[s][s] += 1.0;
+        for &(s_p, _, p) in model.transitions(s, pi[s]) {
+            system[s][s_p] -= gamma * p as f64;
+        }
+        rhs[s] = expected_rewards[s * num_actions + pi[s]];
+    }
+    let value_function = solve_linear_system(system, rhs)
+        .ok_or(LinearProgramError::Singular)?
+        .into_iter()
+        .map(|v| v as f32)
+        .collect();
+
+    Ok(PlanningResult {
+        pi,
+        value_function,
+        duration: start_time.elapsed(),
+        iterations: 1,
+        // Résolution exacte : pas de critère d'arrêt approché
+        converged: true,
+        average_reward: None,
+        policy_iteration_stats: None,
+    })
+}
+
+// Simplexe en deux phases sur un tableau dense, avec la règle de Bland pour éviter le cyclage :
+// maximise c^T x sous A x = b, x >= 0, avec b >= 0.
+pub fn simplex_maximize(a: Vec<Vec<f64>>, b: Vec<f64>, c: Vec<f64>) -> Result<Vec<f64>, LinearProgramError> {
+    let num_constraints = a.len();
+    let num_variables = c.len();
+    let num_columns = num_variables + num_constraints;
+    let rhs_column = num_columns;
+
+    // Phase 1 : une variable artificielle par contrainte, on minimise leur somme
+    let mut tableau = a.into_iter().zip(b).enumerate().map(|(i, (mut row, b_i))| {
+        assert!(b_i >= 0.0, "simplex_maximize expects a non-negative right-hand side");
+        row.resize(num_columns + 1, 0.0);
+        row[num_variables + i] = 1.0;
+        row[rhs_column] = b_i;
+        row
+    }).collect::<Vec<Vec<f64>>>();
+    let mut basis = (num_variables..num_columns).collect::<Vec<usize>>();
+
+    let mut objective = vec![0.0; num_columns + 1];
+    for row in tableau.iter() {
+        for (j, value) in row.iter().enumerate() {
+            if j < num_variables || j == rhs_column {
+                objective[j] -= value;
+            }
+        }
+    }
+    run_simplex(&mut tableau, &mut objective, &mut basis, num_columns)?;
+    if objective[rhs_column] < -1e-7 {
+        return Err(LinearProgramError::Infeasible);
+    }
+
+    // Sortir de la base les variables artificielles restantes (à 0), ou retirer les contraintes redondantes
+    let mut i = 0;
+    while i < tableau.len() {
+        if basis[i] >= num_variables {
+            match (0..num_variables).find(|&j| tableau[i][j].abs() > EPSILON) {
+                Some(j) => pivot(&mut tableau, &mut objective, &mut basis, i, j),
+                None => {
+                    tableau.remove(i);
+                    basis.remove(i);
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+
+    // Phase 2 : objectif réel, colonnes artificielles interdites
+    let mut objective = vec![0.0; num_columns + 1];
+    for j in 0..num_variables {
+        objective[j] = -c[j];
+    }
+    for (i, row) in tableau.iter().enumerate() {
+        let cost = c[basis[i]];
+        if cost != 0.0 {
+            for (j, value) in row.iter().enumerate() {
+                objective[j] += cost * value;
+            }
+        }
+    }
+    run_simplex(&mut tableau, &mut objective, &mut basis, num_variables)?;
+
+    let mut x = vec![0.0; num_variables];
+    for (i, row) in tableau.iter().enumerate() {
+        x[basis[i]] = row[rhs_column];
+    }
+    Ok(x)
+}
+
+// Itère jusqu'à l'optimum en ne faisant entrer que les colonnes d'indice < `num_allowed_columns`
+fn run_simplex(
+    tableau: &mut [Vec<f64>],
+    objective: &mut [f64],
+    basis: &mut [usize],
+    num_allowed_columns: usize,
+) -> Result<(), LinearProgramError> {
+    let rhs_column = objective.len() - 1;
+    loop {
+        let Some(entering) = (0..num_allowed_columns).find(|&j| objective[j] < -EPSILON) else {
+            return Ok(());
+        };
+        let leaving = (0..tableau.len())
+            .filter(|&i| tableau[i][entering] > EPSILON)
+            .min_by(|&i1, &i2| {
+                let ratio1 = tableau[i1][rhs_column] / tableau[i1][entering];
+                let ratio2 = tableau[i2][rhs_column] / tableau[i2][entering];
+                ratio1.partial_cmp(&ratio2).unwrap().then(basis[i1].cmp(&basis[i2]))
+            })
+            .ok_or(LinearProgramError::Unbounded)?;
+        pivot(tableau, objective, basis, leaving, entering);
+    }
+}
+
+fn pivot(tableau: &mut [Vec<f64>], objective: &mut [f64], basis: &mut [usize], row: usize, column: usize) {
+    let pivot_value = tableau[row][column];
+    for value in tableau[row].iter_mut() {
+        *value /= pivot_value;
+    }
+    let pivot_row = tableau[row].clone();
+    for (i, other_row) in tableau.iter_mut().enumerate() {
+        let factor = other_row[column];
+        if i != row && factor != 0.0 {
+            for (value, pivot_row_value) in other_row.iter_mut().zip(pivot_row.iter()) {
+                *value -= factor * pivot_row_value;
+            }
+        }
+    }
+    let factor = objective[column];
+    if factor != 0.0 {
+        for (value, pivot_row_value) in objective.iter_mut().zip(pivot_row.iter()) {
+            *value -= factor * pivot_row_value;
+        }
+    }
+    basis[row] = column;
+}
+
+// Élimination de Gauss avec pivot partiel
+fn solve_linear_system(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let n = rhs.len();
+    for column in 0..n {
+        let pivot_row = (column..n).max_by(|&i1, &i2| matrix[i1][column].abs().partial_cmp(&matrix[i2][column].abs()).unwrap())?;
+        if matrix[pivot_row][column].abs() < EPSILON {
+            return None;
+        }
+        matrix.swap(column, pivot_row);
+        rhs.swap(column, pivot_row);
+        let (upper_rows, lower_rows) = matrix.split_at_mut(column + 1);
+        let pivot_row = &upper_rows[column];
+        for (offset, row) in lower_rows.iter_mut().enumerate() {
+            let factor = row[column] / pivot_row[column];
+            if factor != 0.0 {
+                for (value, pivot_value) in row[column..].iter_mut().zip(pivot_row[column..].iter()) {
+                    *value -= factor * pivot_value;
+                }
+                rhs[column + 1 + offset] -= factor * rhs[column];
+            }
+        }
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum = ((i + 1)..n).fold(0.0, |total, j| total + matrix[i][j] * x[j]);
+        x[i] = (rhs[i] - sum) / matrix[i][i];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undiscounted_loop_has_no_optimal_solution() {
+        // Un seul état qui boucle sur lui-même : avec gamma = 1, la contrainte x - x = 1 n'a pas de solution
+        let model = SparseTransitionModel::from_parts(1, 1, vec![1.0], vec![0, 1], vec![(0, 0, 1.0)]);
+        assert_eq!(linear_programming_with_model(&model, 1.0), Err(LinearProgramError::Infeasible));
+        assert!(linear_programming_with_model(&model, 0.5).is_ok_and(|result| (result.value_function[0] - 2.0).abs() < 1e-5));
+    }
+}
@@ -1,57 +1,53 @@
-﻿use std::time::{SystemTime, UNIX_EPOCH};
-use crate::contracts::model_free_env::ModelFreeEnv;
+use std::collections::HashMap;
+use crate::contracts::environment::Environment;
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
 use rand::prelude::SliceRandom;
+use rand::Rng;
 
-pub fn monte_carlo_exploring_starts<TEnv: ModelFreeEnv>(
+pub fn monte_carlo_exploring_starts(
+    env: &mut impl Environment,
     num_episodes: usize,
     gamma: f32,
-) -> Vec<Vec<f32>> {
-    let mut q_values = vec![vec![0.0; TEnv::num_actions()]; TEnv::num_states()];
-    let mut returns = vec![vec![]; TEnv::num_states()]; // Historique des retours pour chaque état-action
-    let mut env = TEnv::new();
-    let mut rng = rand::thread_rng();
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut returns = vec![vec![vec![]; env.num_actions()]; env.num_states()]; // Historique des retours pour chaque état-action
 
-    let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64;
+    let mut recorder = LearningRecorder::new(env.num_states());
 
     for _ in 0..num_episodes {
         env.reset();
 
-        // Initialisation des épisodes avec des états et actions aléatoires
-        let s = env.state_id();
-        let available_actions = env.available_actions();
-        let a = *available_actions.choose(&mut rng).unwrap(); // Choisir une action aléatoire pour "exploring starts"
-
-        let mut episode = vec![(s, a)]; // Episode sous forme (état, action)
-
+        // Episode sous forme (état, action, récompense), avec des actions aléatoires pour "exploring starts"
+        let mut episode = vec![];
         while !env.is_game_over() {
             let s = env.state_id();
             let available_actions = env.available_actions();
-            let a = *available_actions.choose(&mut rng).unwrap(); // Choisir une action aléatoire
-            env.step(a);
-            episode.push((s, a));
+            let a = *available_actions.choose(rng).unwrap(); // Choisir une action aléatoire
+            recorder.record_step(s, &available_actions);
+            let r = env.step(a).reward;
+            episode.push((s, a, r));
+        }
+        recorder.end_episode(env.score(), &q_values);
+
+        let mut first_visits = HashMap::new();
+        for (t, (s, a, _)) in episode.iter().enumerate() {
+            first_visits.entry((*s, *a)).or_insert(t);
         }
 
         // Calculer la somme des récompenses futures pour cet épisode
-        let mut G = 0.0;
-        let mut visited = std::collections::HashSet::new();
-        for &(state, action) in episode.iter().rev() {
-            if visited.contains(&(state, action)) {
+        let mut g = 0.0;
+        for (t, &(state, action, reward)) in episode.iter().enumerate().rev() {
+            g = gamma * g + reward;
+            if first_visits[&(state, action)] != t {
                 continue;
             }
-            visited.insert((state, action));
-
-            // Récompense immédiate : ici on suppose que la fonction score() donne la récompense de l'étape
-            let reward = env.score();
-
-            G = gamma * G + reward;
 
             // Mettre à jour les valeurs de q
-            let q = &mut q_values[state][action];
-            returns[state].push(G);
-            *q = returns[state].iter().sum::<f32>() / returns[state].len() as f32;
+            returns[state][action].push(g);
+            q_values[state][action] = returns[state][action].iter().sum::<f32>() / returns[state][action].len() as f32;
         }
     }
 
-    println!("time : {}", SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64 - start_time);
-    q_values
+    recorder.finish(q_values)
 }
@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+use crate::contracts::environment::Environment;
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::epsilon_greedy_action;
+use rand::Rng;
+
+// SARSA à n pas. Le tampon circulaire garde les n derniers (état, action, récompense) :
+// n = 1 redonne SARSA, n = usize::MAX (le tampon ne se vide qu'en fin d'épisode) redonne Monte Carlo.
+pub fn n_step_sarsa(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    n: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    assert!(n >= 1, "n must be at least 1");
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut buffer = VecDeque::with_capacity(n.min(1024));
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        buffer.clear();
+        if env.is_game_over() {
+            recorder.end_episode(env.score(), &q_values);
+            continue;
+        }
+        let mut s = env.state_id();
+        let mut available_actions = env.available_actions();
+        let mut a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+
+        // Seul un état terminal annule le retour restant ; une troncature repart de Q(S, A) de l'état atteint
+        let mut terminated = false;
+
+        loop {
+            if !env.is_game_over() {
+                recorder.record_step(s, &available_actions);
+                let transition = env.step(a);
+                terminated = transition.terminated;
+                buffer.push_back((s, a, transition.reward));
+                if !terminated {
+                    s = transition.next_state;
+                    available_actions = env.available_actions();
+                    a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+                }
+            }
+
+            if buffer.is_empty() {
+                break;
+            }
+            if buffer.len() < n && !env.is_game_over() {
+                continue;
+            }
+
+            // G = R_{tau+1} + ... + gamma^(n-1) R_{tau+n} (+ gamma^n Q(S_{tau+n}, A_{tau+n}) si l'épisode continue)
+            let mut g = 0.0;
+            let mut discount = 1.0;
+            for (_, _, r) in buffer.iter() {
+                g += discount * r;
+                discount *= gamma;
+            }
+            if !terminated {
+                g += discount * q_values[s][a];
+            }
+
+            let (s_tau, a_tau, _) = buffer.pop_front().unwrap();
+            q_values[s_tau][a_tau] += learning_rate * (g - q_values[s_tau][a_tau]);
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::algorithms::sarsa::sarsa;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn one_step_equals_sarsa() {
+        let n_step = n_step_sarsa(&mut LineWorld::<5>::new(), 200, 1, 0.1, 0.9, 0.2, &mut StdRng::seed_from_u64(3));
+        let one_step = sarsa(&mut LineWorld::<5>::new(), 200, 0.1, 0.9, 0.2, &mut StdRng::seed_from_u64(3));
+        assert_eq!(n_step.q_values, one_step.q_values);
+        assert_eq!(n_step.steps, one_step.steps);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+use crate::contracts::environment::Environment;
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::{epsilon_greedy_action, epsilon_greedy_probabilities};
+use rand::Rng;
+
+// Tree Backup à n pas. La politique cible est epsilon-greedy avec `target_epsilon` sur les actions disponibles :
+// n = 1 redonne Expected SARSA, n = usize::MAX ne met à jour qu'en fin d'épisode.
+#[allow(clippy::too_many_arguments)]
+pub fn n_step_tree_backup(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    n: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    target_epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    assert!(n >= 1, "n must be at least 1");
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    // Tampon circulaire de (état, actions disponibles, action, récompense)
+    let mut buffer: VecDeque<(usize, Vec<usize>, usize, f32)> = VecDeque::with_capacity(n.min(1024));
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        buffer.clear();
+        // Seul un état terminal annule le retour restant ; une troncature repart de l'état courant
+        let mut terminated = false;
+
+        loop {
+            if !env.is_game_over() {
+                let s = env.state_id();
+                let available_actions = env.available_actions();
+                let a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+                recorder.record_step(s, &available_actions);
+                let transition = env.step(a);
+                terminated = transition.terminated;
+                buffer.push_back((s, available_actions, a, transition.reward));
+            }
+
+            if buffer.is_empty() {
+                break;
+            }
+            if buffer.len() < n && !env.is_game_over() {
+                continue;
+            }
+
+            // Retour calculé de la fin du tampon vers son début :
+            // G = R_{k+1} + gamma * (sum_{a != A_{k+1}} π(a|S_{k+1}) Q(S_{k+1}, a) + π(A_{k+1}|S_{k+1}) G)
+            let mut g = if terminated {
+                0.0
+            } else {
+                let s_p = env.state_id();
+                epsilon_greedy_probabilities(&q_values[s_p], &env.available_actions(), target_epsilon)
+                    .iter()
+                    .zip(q_values[s_p].iter())
+                    .map(|(p, q)| p * q)
+                    .sum::<f32>()
+            };
+            let mut next: Option<(usize, &Vec<usize>, usize)> = None;
+            for (s, available_actions, a, r) in buffer.iter().rev() {
+                if let Some((s_next, available_actions_next, a_next)) = next {
+                    let pi = epsilon_greedy_probabilities(&q_values[s_next], available_actions_next, target_epsilon);
+                    let expected_others = pi.iter()
+                        .zip(q_values[s_next].iter())
+                        .enumerate()
+                        .filter(|(b, _)| *b != a_next)
+                        .map(|(_, (p, q))| p * q)
+                        .sum::<f32>();
+                    g = r + gamma * (expected_others + pi[a_next] * g);
+                } else {
+                    g = r + gamma * g;
+                }
+                next = Some((*s, available_actions, *a));
+            }
+
+            let (s_tau, _, a_tau, _) = buffer.pop_front().unwrap();
+            q_values[s_tau][a_tau] += learning_rate * (g - q_values[s_tau][a_tau]);
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::algorithms::expected_sarsa::expected_sarsa;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn one_step_equals_expected_sarsa() {
+        let tree_backup = n_step_tree_backup(&mut LineWorld::<5>::new(), 200, 1, 0.1, 0.9, 0.2, 0.1, &mut StdRng::seed_from_u64(3));
+        let expected = expected_sarsa(&mut LineWorld::<5>::new(), 200, 0.1, 0.9, 0.2, 0.1, &mut StdRng::seed_from_u64(3));
+        assert_eq!(tree_backup.steps, expected.steps);
+        for (q_tree_backup, q_expected) in tree_backup.q_values.iter().flatten().zip(expected.q_values.iter().flatten()) {
+            assert!((q_tree_backup - q_expected).abs() < 1e-5, "{} vs {}", q_tree_backup, q_expected);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::contracts::environment::Environment;
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::{epsilon_greedy_action, epsilon_greedy_probabilities, greedy_action};
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportanceSampling {
+    Ordinary,
+    Weighted,
+}
+
+// Contrôle Monte Carlo off-policy : politique de comportement epsilon-soft, politique cible greedy.
+// Avec `Ordinary`, C(s,a) compte les retours observés ; avec `Weighted`, il cumule les poids W.
+pub fn off_policy_monte_carlo(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    gamma: f32,
+    epsilon: f32,
+    importance_sampling: ImportanceSampling,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut c = vec![vec![0.0f32; env.num_actions()]; env.num_states()];
+    let mut pi = vec![0usize; env.num_states()];
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+
+        // Episode sous forme (état, actions disponibles, action, récompense, b(a|s))
+        let mut episode = vec![];
+        while !env.is_game_over() {
+            let s = env.state_id();
+            let available_actions = env.available_actions();
+            let a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+            recorder.record_step(s, &available_actions);
+            let b_a = epsilon_greedy_probabilities(&q_values[s], &available_actions, epsilon)[a];
+            let r = env.step(a).reward;
+            episode.push((s, available_actions, a, r, b_a));
+        }
+        recorder.end_episode(env.score(), &q_values);
+
+        let mut g = 0.0;
+        let mut w = 1.0f32;
+        for (s, available_actions, a, r, b_a) in episode.iter().rev() {
+            g = gamma * g + r;
+
+            match importance_sampling {
+                ImportanceSampling::Ordinary => {
+                    c[*s][*a] += 1.0;
+                    q_values[*s][*a] += (w * g - q_values[*s][*a]) / c[*s][*a];
+                }
+                ImportanceSampling::Weighted => {
+                    c[*s][*a] += w;
+                    q_values[*s][*a] += w / c[*s][*a] * (g - q_values[*s][*a]);
+                }
+            }
+            pi[*s] = greedy_action(&q_values[*s], available_actions);
+
+            // π est déterministe : π(a|s) vaut 1 si a est l'action greedy, 0 sinon
+            if *a != pi[*s] {
+                if importance_sampling == ImportanceSampling::Weighted {
+                    break;
+                }
+                // En échantillonnage ordinaire les retours de poids nul comptent quand même dans la moyenne
+                w = 0.0;
+            } else {
+                w /= b_a;
+            }
+        }
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn both_importance_samplings_converge_on_line_world() {
+        for importance_sampling in [ImportanceSampling::Ordinary, ImportanceSampling::Weighted] {
+            let result = off_policy_monte_carlo(&mut LineWorld::<5>::new(), 2_000, 0.9, 0.3, importance_sampling, &mut StdRng::seed_from_u64(0));
+            assert_eq!(result.pi[1..4], [1, 1, 1], "{:?}", importance_sampling);
+            // Valeurs de la politique greedy : 0.9^k pour k pas restants avant la sortie de droite
+            for (s, expected) in [(1, 0.81), (2, 0.9), (3, 1.0)] {
+                assert!((result.q_values[s][1] - expected).abs() < 0.05, "{:?}: Q({}, right) = {}", importance_sampling, s, result.q_values[s][1]);
+            }
+        }
+    }
+}
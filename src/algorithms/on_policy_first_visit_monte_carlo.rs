@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use crate::contracts::environment::Environment;
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::epsilon_greedy_probabilities;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+// Contrôle Monte Carlo on-policy first-visit avec une politique epsilon-soft explicite π(s, a).
+// Les retours sont moyennés de façon incrémentale, sans garder l'historique des retours.
+// La politique epsilon-soft finale est renvoyée dans `soft_policy`.
+pub fn on_policy_first_visit_monte_carlo(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    gamma: f32,
+    epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let num_states = env.num_states();
+    let num_actions = env.num_actions();
+    let mut q_values = vec![vec![0.0; num_actions]; num_states];
+    let mut visit_counts = vec![vec![0u32; num_actions]; num_states];
+    let mut pi = vec![vec![1.0 / num_actions as f32; num_actions]; num_states];
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+
+        // Episode sous forme (état, actions disponibles, action, récompense)
+        let mut episode = vec![];
+        while !env.is_game_over() {
+            let s = env.state_id();
+            let available_actions = env.available_actions();
+            // π(s, .) restreinte aux actions disponibles, uniforme si elle ne leur donne aucun poids
+            let weights = available_actions.iter().map(|&a| pi[s][a]).collect::<Vec<f32>>();
+            let a = match WeightedIndex::new(&weights) {
+                Ok(distribution) => available_actions[distribution.sample(rng)],
+                Err(_) => *available_actions.choose(rng).unwrap(),
+            };
+            recorder.record_step(s, &available_actions);
+            let r = env.step(a).reward;
+            episode.push((s, available_actions, a, r));
+        }
+        recorder.end_episode(env.score(), &q_values);
+
+        let mut first_visits = HashMap::new();
+        for (t, (s, _, a, _)) in episode.iter().enumerate() {
+            first_visits.entry((*s, *a)).or_insert(t);
+        }
+
+        let mut g = 0.0;
+        for (t, (s, available_actions, a, r)) in episode.iter().enumerate().rev() {
+            g = gamma * g + r;
+            if first_visits[&(*s, *a)] != t {
+                continue;
+            }
+
+            visit_counts[*s][*a] += 1;
+            q_values[*s][*a] += (g - q_values[*s][*a]) / visit_counts[*s][*a] as f32;
+
+            pi[*s] = epsilon_greedy_probabilities(&q_values[*s], available_actions, epsilon);
+        }
+    }
+
+    LearningResult {
+        soft_policy: Some(pi),
+        ..recorder.finish(q_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn soft_policy_favours_moving_right_on_line_world() {
+        let result = on_policy_first_visit_monte_carlo(&mut LineWorld::<5>::new(), 1_000, 0.9, 0.1, &mut StdRng::seed_from_u64(0));
+        assert_eq!(result.pi[1..4], [1, 1, 1]);
+        let soft_policy = result.soft_policy.unwrap();
+        for pi_s in &soft_policy[1..4] {
+            // 1 - epsilon + epsilon / 2 sur l'action greedy, epsilon / 2 sur l'autre
+            assert!((pi_s[1] - 0.95).abs() < 1e-6 && (pi_s[0] - 0.05).abs() < 1e-6, "{:?}", pi_s);
+        }
+    }
+}
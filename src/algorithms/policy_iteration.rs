@@ -1,87 +1,161 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-use crate::contracts::mdp_env::MDPEnv;
+use std::time::Instant;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+use crate::algorithms::training_result::{PlanningResult, PolicyIterationStats};
 
-pub fn policy_iteration<TEnv: MDPEnv>(gamma: f32, theta: f32) -> (Vec<usize>, Vec<f32>) {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueUpdate {
+    // Mise à jour en place : les nouvelles valeurs servent dès le même balayage
+    GaussSeidel,
+    // Mise à jour synchrone : chaque balayage ne lit que les valeurs du balayage précédent
+    Jacobi,
+}
 
-    let mut pi = vec![0usize; TEnv::num_states()];
-    let mut value_function = vec![0.0; TEnv::num_states()];
+pub fn policy_iteration(mdp: &impl Mdp, gamma: f32, theta: f32, max_sweeps: usize) -> PlanningResult {
+    modified_policy_iteration(mdp, gamma, theta, None, ValueUpdate::GaussSeidel, max_sweeps)
+}
 
-    let num_states = TEnv::num_states();
-    let num_actions = TEnv::num_actions();
-    let num_rewards = TEnv::num_rewards();
-    let rewards = (0..num_rewards).map(|r_index| TEnv::reward(r_index)).collect::<Vec<f32>>();
-    let num_floats = num_states * num_actions * num_states * num_rewards;
+// Policy iteration modifiée : `evaluation_sweeps = Some(k)` limite l'évaluation à k balayages par amélioration,
+// `None` évalue jusqu'à delta < theta comme `policy_iteration`.
+// On s'arrête quand la politique est stable et que le dernier balayage a bougé V de moins de theta,
+// ou après `max_sweeps` balayages d'évaluation au total (`converged` faux). Le détail est dans `policy_iteration_stats`.
+pub fn modified_policy_iteration(
+    mdp: &impl Mdp,
+    gamma: f32,
+    theta: f32,
+    evaluation_sweeps: Option<usize>,
+    value_update: ValueUpdate,
+    max_sweeps: usize,
+) -> PlanningResult {
+    let model = SparseTransitionModel::from_mdp(mdp);
+    modified_policy_iteration_with_model(&model, gamma, theta, evaluation_sweeps, value_update, max_sweeps)
+}
 
-    let mut cached_transition_probabilities = vec![42f32; num_floats];
+pub fn modified_policy_iteration_with_model(
+    model: &SparseTransitionModel,
+    gamma: f32,
+    theta: f32,
+    evaluation_sweeps: Option<usize>,
+    value_update: ValueUpdate,
+    max_sweeps: usize,
+) -> PlanningResult {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let mut pi = vec![0usize; num_states];
+    let mut value_function = vec![0.0; num_states];
+    let mut stats = PolicyIterationStats::default();
 
-    for s in 0..num_states {
-        let index = s * num_actions * num_states * num_rewards;
-        for a in 0..num_actions {
-            let index = index + a * num_states * num_rewards;
-            for s_p in 0..num_states {
-                let index = index + s_p * num_rewards;
-                for r_index in 0..num_rewards {
-                    let index = index + r_index;
-                    cached_transition_probabilities[index] = TEnv::transition_probability(s, a, s_p, r_index);
-                }
-            }
-        }
-    }
-
-    let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64;
+    let start_time = Instant::now();
 
-    loop {
+    let mut previous_values = value_function.clone();
+    let mut converged = false;
+    while stats.evaluation_sweeps < max_sweeps {
         // Policy evaluation
+        let mut delta;
+        let mut sweeps = 0;
         loop {
-            let mut delta = 0.0f32;
+            delta = 0.0f32;
+            if value_update == ValueUpdate::Jacobi {
+                previous_values.copy_from_slice(&value_function);
+            }
             for s in 0..num_states {
                 let v = value_function[s];
-                let a = pi[s];
-                let mut total = 0f32;
-                let s_index = s * num_actions * num_states * num_rewards;
-                for s_p in 0..num_states {
-                    let s_p_index = s_index + a * num_states * num_rewards + s_p * num_rewards;
-                    for r_index in 0..num_rewards {
-                        let index = s_p_index + r_index;
-                        total += cached_transition_probabilities[index] * (rewards[r_index] + gamma * value_function[s_p]);
-                    }
-                }
-                value_function[s] = total;
+                value_function[s] = match value_update {
+                    ValueUpdate::GaussSeidel => model.q_value(s, pi[s], &value_function, gamma),
+                    ValueUpdate::Jacobi => model.q_value(s, pi[s], &previous_values, gamma),
+                };
                 delta = delta.max((v - value_function[s]).abs());
             }
-            if delta < theta {
+            sweeps += 1;
+            if delta < theta || evaluation_sweeps.is_some_and(|k| sweeps >= k) || stats.evaluation_sweeps + sweeps >= max_sweeps {
                 break;
             }
         }
+        stats.evaluation_sweeps += sweeps;
 
         // Policy improvement
         let mut policy_stable = true;
         for (s, pi_s) in pi.iter_mut().enumerate() {
             let old_action = *pi_s;
-            let s_index = s * num_actions * num_states * num_rewards;
-            *pi_s = (0..num_actions).map(|a| {
-                let mut total = 0f32;
-                let a_index = s_index + a * num_states * num_rewards;
-                for s_p in 0..num_states {
-                    let s_p_index = a_index + s_p * num_rewards;
-                    for r_index in 0..num_rewards {
-                        let index = s_p_index + r_index;
-                        total += cached_transition_probabilities[index] * (rewards[r_index] + gamma * value_function[s_p]);
-                    }
-                }
-
-                (a, total)
-            }).max_by(|(_, q1), (_, q2)| q1.partial_cmp(q2).unwrap()).unwrap().0;
+            *pi_s = (0..num_actions)
+                .map(|a| (a, model.q_value(s, a, &value_function, gamma)))
+                .max_by(|(_, q1), (_, q2)| q1.partial_cmp(q2).unwrap()).unwrap().0;
 
             if old_action != *pi_s {
                 policy_stable = false;
             }
         }
+        stats.improvements += 1;
 
-        if policy_stable {
+        if policy_stable && delta < theta {
+            converged = true;
             break;
         }
     }
-    println!("time : {}", SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64 - start_time);
-    (pi, value_function)
-}
\ No newline at end of file
+    PlanningResult {
+        pi,
+        value_function,
+        duration: start_time.elapsed(),
+        iterations: stats.improvements,
+        converged,
+        average_reward: None,
+        policy_iteration_stats: Some(stats),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::envs::grid_world::GridWorld;
+    use crate::models::random_mdp::random_mdp;
+
+    fn max_difference(v1: &[f32], v2: &[f32]) -> f32 {
+        v1.iter().zip(v2.iter()).map(|(x1, x2)| (x1 - x2).abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn reports_whether_the_sweep_limit_was_reached() {
+        let model = SparseTransitionModel::from_mdp_env::<GridWorld<4, 4>>();
+        let converged = modified_policy_iteration_with_model(&model, 0.999, 0.001, Some(3), ValueUpdate::Jacobi, 10_000);
+        assert!(converged.converged);
+        let stats = converged.policy_iteration_stats.unwrap();
+        assert_eq!(stats.improvements, converged.iterations);
+        assert!(stats.evaluation_sweeps <= 10_000);
+
+        let truncated = modified_policy_iteration_with_model(&model, 0.999, 0.001, None, ValueUpdate::GaussSeidel, 2);
+        assert!(!truncated.converged);
+        assert_eq!(truncated.policy_iteration_stats.unwrap().evaluation_sweeps, 2);
+    }
+
+    #[test]
+    fn gauss_seidel_and_jacobi_reach_the_same_solution() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let model = random_mdp(20, 3, 4, 5, &mut rng);
+            let gauss_seidel = modified_policy_iteration_with_model(&model, 0.9, 1e-6, None, ValueUpdate::GaussSeidel, 100_000);
+            let jacobi = modified_policy_iteration_with_model(&model, 0.9, 1e-6, None, ValueUpdate::Jacobi, 100_000);
+            assert!(gauss_seidel.converged && jacobi.converged);
+            assert_eq!(gauss_seidel.pi, jacobi.pi);
+            assert!(max_difference(&gauss_seidel.value_function, &jacobi.value_function) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn truncated_evaluation_reaches_the_fully_evaluated_policy() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..5 {
+            let model = random_mdp(20, 3, 4, 5, &mut rng);
+            let full = modified_policy_iteration_with_model(&model, 0.9, 1e-6, None, ValueUpdate::GaussSeidel, 100_000);
+            for evaluation_sweeps in [1, 3] {
+                for value_update in [ValueUpdate::GaussSeidel, ValueUpdate::Jacobi] {
+                    let truncated = modified_policy_iteration_with_model(&model, 0.9, 1e-6, Some(evaluation_sweeps), value_update, 100_000);
+                    assert!(truncated.converged);
+                    assert_eq!(truncated.pi, full.pi, "k = {}, {:?}", evaluation_sweeps, value_update);
+                    assert!(max_difference(&truncated.value_function, &full.value_function) < 1e-4);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap};
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::epsilon_greedy_action;
+use rand::Rng;
+
+struct PrioritizedPair {
+    priority: f32,
+    state: usize,
+    action: usize,
+}
+
+impl PartialEq for PrioritizedPair {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PrioritizedPair {}
+
+impl PartialOrd for PrioritizedPair {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PrioritizedPair {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.total_cmp(&other.priority)
+    }
+}
+
+// File de priorité sur les paires (s, a), indexée par |erreur TD|.
+// Une paire déjà présente garde sa plus grande priorité ; les entrées périmées du tas sont ignorées au dépilement.
+struct PriorityQueue {
+    heap: BinaryHeap<PrioritizedPair>,
+    priorities: Vec<Vec<f32>>,
+}
+
+impl PriorityQueue {
+    fn new(num_states: usize, num_actions: usize) -> Self {
+        PriorityQueue {
+            heap: BinaryHeap::new(),
+            priorities: vec![vec![0.0; num_actions]; num_states],
+        }
+    }
+
+    fn push(&mut self, state: usize, action: usize, priority: f32) {
+        if priority > self.priorities[state][action] {
+            self.priorities[state][action] = priority;
+            self.heap.push(PrioritizedPair { priority, state, action });
+        }
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize)> {
+        while let Some(PrioritizedPair { priority, state, action }) = self.heap.pop() {
+            if priority == self.priorities[state][action] {
+                self.priorities[state][action] = 0.0;
+                return Some((state, action));
+            }
+        }
+        None
+    }
+}
+
+fn max_q(q_s: &[f32]) -> f32 {
+    *q_s.iter().max_by(|q1, q2| q1.partial_cmp(q2).unwrap()).unwrap()
+}
+
+// Prioritized sweeping : modèle déterministe appris (s, a) -> (r, s') et prédécesseurs de chaque état,
+// les mises à jour de planification partent des transitions les plus surprenantes.
+#[allow(clippy::too_many_arguments)]
+pub fn prioritized_sweeping(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    planning_steps: usize,
+    theta: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut model: Vec<Vec<Option<(f32, usize)>>> = vec![vec![None; env.num_actions()]; env.num_states()];
+    let mut predecessors = vec![BTreeSet::new(); env.num_states()];
+    let mut queue = PriorityQueue::new(env.num_states(), env.num_actions());
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        while !env.is_game_over() {
+            let s = env.state_id();
+            let available_actions = env.available_actions();
+            let a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, .. } = env.step(a);
+
+            model[s][a] = Some((r, s_p));
+            predecessors[s_p].insert((s, a));
+
+            let priority = (r + gamma * max_q(&q_values[s_p]) - q_values[s][a]).abs();
+            if priority > theta {
+                queue.push(s, a, priority);
+            }
+
+            for _ in 0..planning_steps {
+                let Some((s, a)) = queue.pop() else {
+                    break;
+                };
+                let (r, s_p) = model[s][a].unwrap();
+                q_values[s][a] += learning_rate * (r + gamma * max_q(&q_values[s_p]) - q_values[s][a]);
+
+                let max_q_s = max_q(&q_values[s]);
+                for &(s_pred, a_pred) in predecessors[s].iter() {
+                    let (r_pred, _) = model[s_pred][a_pred].unwrap();
+                    let priority = (r_pred + gamma * max_q_s - q_values[s_pred][a_pred]).abs();
+                    if priority > theta {
+                        queue.push(s_pred, a_pred, priority);
+                    }
+                }
+            }
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::grid_world::GridWorld;
+
+    // Les prédécesseurs sont parcourus dans un ordre fixe : les égalités de priorité ne dépendent pas du hachage
+    #[test]
+    fn same_seed_gives_bit_identical_q_values() {
+        let run = |seed| {
+            let q_values = prioritized_sweeping(&mut GridWorld::<4, 4>::new(), 200, 0.1, 0.999, 0.3, 5, 1e-4, &mut StdRng::seed_from_u64(seed)).q_values;
+            q_values.iter().flatten().map(|q: &f32| q.to_bits()).collect::<Vec<u32>>()
+        };
+        assert_eq!(run(7), run(7));
+    }
+}
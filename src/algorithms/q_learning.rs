@@ -1,39 +1,61 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-use crate::contracts::model_free_env::ModelFreeEnv;
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
 use rand::prelude::SliceRandom;
 use rand::Rng;
 
-pub fn q_learning<TEnv: ModelFreeEnv>(
+pub fn q_learning(
+    env: &mut impl Environment,
     num_episodes: usize,
     learning_rate: f32,
     gamma: f32,
     epsilon: f32,
-) -> Vec<Vec<f32>> {
-    let mut q_values = vec![vec![0.0; TEnv::num_actions()]; TEnv::num_states()];
-    let mut env = TEnv::new();
-    let mut rng = rand::thread_rng();
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
 
-    let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64;
+    let mut recorder = LearningRecorder::new(env.num_states());
 
     for _ in 0..num_episodes {
         env.reset();
         while !env.is_game_over() {
             let s = env.state_id();
             let available_actions = env.available_actions();
+            recorder.record_step(s, &available_actions);
             let a = if rng.gen::<f32>() < epsilon {
-                *available_actions.choose(&mut rng).unwrap()
+                *available_actions.choose(rng).unwrap()
             } else {
-                q_values[s].iter().enumerate().max_by(|(_, q1), (_, q2)| q1.partial_cmp(q2).unwrap()).unwrap().0
+                *available_actions.iter().max_by(|a1, a2| q_values[s][**a1].partial_cmp(&q_values[s][**a2]).unwrap()).unwrap()
             };
-            let previous_score = env.score();
-            env.step(a);
-            let r = env.score() - previous_score;
-            let s_p = env.state_id();
+            let Transition { reward: r, next_state: s_p, .. } = env.step(a);
             let q_s_p = q_values[s_p].iter().max_by(|q1, q2| q1.partial_cmp(q2).unwrap()).unwrap();
             q_values[s][a] += learning_rate * (r + gamma * q_s_p - q_values[s][a]);
         }
+        recorder.end_episode(env.score(), &q_values);
     }
-    
-    println!("time : {}", SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64 - start_time);
-    q_values
-}
\ No newline at end of file
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::grid_world::GridWorld;
+
+    fn run(seed: u64) -> Vec<u32> {
+        let q_values = q_learning(&mut GridWorld::<4, 4>::new(), 1_000, 0.1, 0.999, 0.3, &mut StdRng::seed_from_u64(seed)).q_values;
+        q_values.iter().flatten().map(|q| q.to_bits()).collect()
+    }
+
+    #[test]
+    fn same_seed_gives_bit_identical_q_values() {
+        assert_eq!(run(7), run(7));
+    }
+
+    #[test]
+    fn different_seeds_give_different_q_values() {
+        assert_ne!(run(7), run(8));
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::Instant;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+use crate::algorithms::training_result::PlanningResult;
+
+// Poids de la transition réelle dans P_τ = τ P + (1 - τ) I : rend la chaîne apériodique
+// (sans changer ni rho ni les politiques optimales), sinon l'itération peut osciller indéfiniment
+const APERIODICITY_WEIGHT: f32 = 0.5;
+
+// Critère de récompense moyenne sur la version continue de l'environnement (cf. `ContinuingTask`),
+// en repartant de `start_state` à chaque fin d'épisode.
+pub fn relative_value_iteration(mdp: &impl Mdp, start_state: usize, theta: f32, reference_state: usize, max_sweeps: usize) -> PlanningResult {
+    let model = SparseTransitionModel::from_mdp(mdp).to_continuing(start_state);
+    relative_value_iteration_with_model(&model, theta, reference_state, max_sweeps)
+}
+
+// Itération sur les valeurs relatives : h <- T h - (T h)(reference_state), avec
+//   (T h)(s) = max_a sum_{s',r} p(s',r|s,a) (r + h(s')).
+// S'arrête quand l'étendue (max - min) de T h - h passe sous `theta`, ou après `max_sweeps` balayages
+// (`converged` faux, par exemple sur un modèle multichaîne où l'étendue ne s'annule jamais). Renvoie la politique greedy et
+// les valeurs relatives h (nulles en `reference_state`), avec le gain moyen par pas rho dans `average_reward`.
+// Le modèle doit être unichaîne et continu : les lignes vides (états terminaux) ne sont pas redirigées.
+pub fn relative_value_iteration_with_model(model: &SparseTransitionModel, theta: f32, reference_state: usize, max_sweeps: usize) -> PlanningResult {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let mut pi = vec![0usize; num_states];
+    let mut relative_values = vec![0.0f32; num_states];
+    let mut rho = 0.0;
+
+    let start_time = Instant::now();
+
+    let mut sweeps = 0;
+    let mut converged = false;
+    while sweeps < max_sweeps {
+        let mut updated_values = vec![0.0f32; num_states];
+        for s in 0..num_states {
+            // En cas d'égalité on garde la première action
+            let (best_action, best_value) = (0..num_actions)
+                .map(|a| (a, model.q_value(s, a, &relative_values, 1.0)))
+                .fold((0, f32::NEG_INFINITY), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+            pi[s] = best_action;
+            updated_values[s] = APERIODICITY_WEIGHT * best_value + (1.0 - APERIODICITY_WEIGHT) * relative_values[s];
+        }
+
+        let (min_difference, max_difference) = updated_values.iter()
+            .zip(relative_values.iter())
+            .map(|(updated, current)| updated - current)
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), difference| (min.min(difference), max.max(difference)));
+        rho = (updated_values[reference_state] - relative_values[reference_state]) / APERIODICITY_WEIGHT;
+
+        let offset = updated_values[reference_state];
+        relative_values = updated_values.iter().map(|v| v - offset).collect();
+
+        sweeps += 1;
+        if max_difference - min_difference < theta {
+            converged = true;
+            break;
+        }
+    }
+    PlanningResult {
+        pi,
+        value_function: relative_values,
+        duration: start_time.elapsed(),
+        iterations: sweeps,
+        converged,
+        average_reward: Some(rho),
+        policy_iteration_stats: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multichain_model_stops_at_the_sweep_limit() {
+        // Deux boucles fermées de gains 1 et 0 : l'étendue de T h - h reste 1, le critère d'arrêt n'est jamais atteint
+        let model = SparseTransitionModel::from_parts(2, 1, vec![0.0, 1.0], vec![0, 1, 2], vec![(0, 1, 1.0), (1, 0, 1.0)]);
+        let result = relative_value_iteration_with_model(&model, 0.0001, 0, 50);
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 50);
+    }
+
+    #[test]
+    fn unichain_model_converges_to_its_gain() {
+        // 0 <-> 1 avec une récompense de 1 en allant vers 1 : gain moyen 0.5
+        let model = SparseTransitionModel::from_parts(2, 1, vec![0.0, 1.0], vec![0, 1, 2], vec![(1, 1, 1.0), (0, 0, 1.0)]);
+        let result = relative_value_iteration_with_model(&model, 0.0001, 0, 10_000);
+        assert!(result.converged);
+        assert!((result.average_reward.unwrap() - 0.5).abs() < 1e-3);
+    }
+}
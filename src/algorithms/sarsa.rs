@@ -0,0 +1,63 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::epsilon_greedy_action;
+use rand::Rng;
+
+pub fn sarsa(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    epsilon: f32,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        if env.is_game_over() {
+            recorder.end_episode(env.score(), &q_values);
+            continue;
+        }
+        let mut s = env.state_id();
+        let mut available_actions = env.available_actions();
+        let mut a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+        while !env.is_game_over() {
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, terminated, .. } = env.step(a);
+
+            // L'état terminal n'a pas d'action suivante : Q(s', a') vaut 0
+            if terminated {
+                q_values[s][a] += learning_rate * (r - q_values[s][a]);
+                break;
+            }
+
+            available_actions = env.available_actions();
+            let a_p = epsilon_greedy_action(&q_values[s_p], &available_actions, epsilon, rng);
+            q_values[s][a] += learning_rate * (r + gamma * q_values[s_p][a_p] - q_values[s][a]);
+            s = s_p;
+            a = a_p;
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn learns_to_move_right_on_line_world() {
+        let result = sarsa(&mut LineWorld::<5>::new(), 500, 0.1, 0.9, 0.1, &mut StdRng::seed_from_u64(0));
+        assert_eq!(result.pi[1..4], [1, 1, 1]);
+        assert!(result.q_values[3][1] > 0.9);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::epsilon_greedy_action;
+use crate::algorithms::eligibility_traces::{EligibilityTraces, TraceKind};
+use rand::Rng;
+
+#[allow(clippy::too_many_arguments)]
+pub fn sarsa_lambda(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    lambda: f32,
+    epsilon: f32,
+    trace_kind: TraceKind,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut traces = EligibilityTraces::new(trace_kind, env.num_states(), env.num_actions());
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        traces.clear();
+        if env.is_game_over() {
+            recorder.end_episode(env.score(), &q_values);
+            continue;
+        }
+        let mut s = env.state_id();
+        let mut available_actions = env.available_actions();
+        let mut a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+        while !env.is_game_over() {
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, terminated, .. } = env.step(a);
+
+            traces.visit(s, a, learning_rate);
+
+            if terminated {
+                let delta = r - q_values[s][a];
+                traces.update(&mut q_values, learning_rate, delta);
+                break;
+            }
+
+            available_actions = env.available_actions();
+            let a_p = epsilon_greedy_action(&q_values[s_p], &available_actions, epsilon, rng);
+            let delta = r + gamma * q_values[s_p][a_p] - q_values[s][a];
+            traces.update(&mut q_values, learning_rate, delta);
+            traces.decay(gamma * lambda);
+            s = s_p;
+            a = a_p;
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn every_trace_kind_learns_to_move_right_on_line_world() {
+        for trace_kind in [TraceKind::Accumulating, TraceKind::Replacing, TraceKind::Dutch] {
+            let result = sarsa_lambda(&mut LineWorld::<5>::new(), 300, 0.1, 0.9, 0.8, 0.1, trace_kind, &mut StdRng::seed_from_u64(0));
+            assert_eq!(result.pi[1..4], [1, 1, 1], "{:?}", trace_kind);
+            assert!(result.q_values[3][1] > 0.9, "{:?}", trace_kind);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::time::{Duration, Instant};
+use crate::algorithms::epsilon_greedy::greedy_action;
+
+// Variation de Q entre deux points de contrôle sous laquelle un apprentissage est considéré comme convergé
+pub const Q_CONVERGENCE_TOLERANCE: f32 = 1e-4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PolicyIterationStats {
+    pub evaluation_sweeps: usize,
+    pub improvements: usize,
+}
+
+// Résultat d'un algorithme de planification sur un modèle connu (programmation dynamique, programmation linéaire)
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanningResult {
+    pub pi: Vec<usize>,
+    pub value_function: Vec<f32>,
+    pub duration: Duration,
+    // Balayages de l'espace d'états (améliorations de la politique pour policy iteration, 1 pour une résolution exacte)
+    pub iterations: usize,
+    // Faux si l'algorithme s'est arrêté sur une limite d'itérations avant son critère d'arrêt
+    pub converged: bool,
+    // Gain moyen par pas rho, pour les algorithmes à critère de récompense moyenne
+    pub average_reward: Option<f32>,
+    // Détail des balayages, pour policy iteration
+    pub policy_iteration_stats: Option<PolicyIterationStats>,
+}
+
+// Résultat de l'induction arrière : une politique et une fonction de valeur par pas de temps restant
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiniteHorizonResult {
+    pub pi: Vec<Vec<usize>>,
+    pub value_function: Vec<Vec<f32>>,
+    pub duration: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EpisodeMetrics {
+    // Score de l'environnement en fin d'épisode, soit le retour non actualisé
+    pub score: f32,
+    pub length: usize,
+}
+
+// Résultat d'un algorithme d'apprentissage sans modèle
+#[derive(Clone, Debug, PartialEq)]
+pub struct LearningResult {
+    // Politique greedy par rapport à Q, restreinte aux actions disponibles observées (0 pour un état jamais visité)
+    pub pi: Vec<usize>,
+    pub q_values: Vec<Vec<f32>>,
+    pub duration: Duration,
+    pub episodes: usize,
+    pub steps: usize,
+    // Une entrée par épisode terminé (vide pour une tâche continue)
+    pub episode_metrics: Vec<EpisodeMetrics>,
+    // Vrai si Q a bougé de moins de Q_CONVERGENCE_TOLERANCE depuis le point de contrôle précédent
+    // (fin d'épisode, ou tous les `CHECKPOINT_STEPS` pas pour une tâche continue)
+    pub converged: bool,
+    // Estimation de rho, pour les algorithmes à critère de récompense moyenne
+    pub average_reward: Option<f32>,
+    // Politique stochastique π(s, a) apprise, pour les algorithmes on-policy à politique explicite
+    pub soft_policy: Option<Vec<Vec<f32>>>,
+}
+
+// Intervalle entre deux points de contrôle de Q pour les tâches continues, qui n'ont pas de fin d'épisode
+pub const CHECKPOINT_STEPS: usize = 1_000;
+
+// Chronomètre et métriques d'un apprentissage sans modèle, à alimenter pas à pas puis à clore avec `finish`
+pub struct LearningRecorder {
+    start_time: Instant,
+    observed_actions: Vec<Option<Vec<usize>>>,
+    steps: usize,
+    episode_length: usize,
+    episode_metrics: Vec<EpisodeMetrics>,
+    checkpoint_q_values: Option<Vec<Vec<f32>>>,
+    last_q_change: f32,
+}
+
+impl LearningRecorder {
+    pub fn new(num_states: usize) -> Self {
+        LearningRecorder {
+            start_time: Instant::now(),
+            observed_actions: vec![None; num_states],
+            steps: 0,
+            episode_length: 0,
+            episode_metrics: Vec::new(),
+            checkpoint_q_values: None,
+            last_q_change: f32::INFINITY,
+        }
+    }
+
+    // À appeler à chaque action jouée, avec l'état et les actions qui y étaient disponibles
+    pub fn record_step(&mut self, state: usize, available_actions: &[usize]) {
+        if self.observed_actions[state].is_none() {
+            self.observed_actions[state] = Some(available_actions.to_vec());
+        }
+        self.steps += 1;
+        self.episode_length += 1;
+    }
+
+    pub fn end_episode(&mut self, score: f32, q_values: &[Vec<f32>]) {
+        self.episode_metrics.push(EpisodeMetrics {
+            score,
+            length: self.episode_length,
+        });
+        self.episode_length = 0;
+        self.checkpoint(q_values);
+    }
+
+    // Mesure la plus grande variation de Q depuis le point de contrôle précédent
+    pub fn checkpoint(&mut self, q_values: &[Vec<f32>]) {
+        match self.checkpoint_q_values.as_mut() {
+            Some(previous_q_values) => {
+                self.last_q_change = 0.0;
+                for (previous_q_s, q_s) in previous_q_values.iter_mut().zip(q_values.iter()) {
+                    for (previous_q, &q) in previous_q_s.iter_mut().zip(q_s.iter()) {
+                        self.last_q_change = self.last_q_change.max((q - *previous_q).abs());
+                        *previous_q = q;
+                    }
+                }
+            }
+            None => self.checkpoint_q_values = Some(q_values.to_vec()),
+        }
+    }
+
+    pub fn finish(self, q_values: Vec<Vec<f32>>) -> LearningResult {
+        let pi = self.observed_actions.iter()
+            .zip(q_values.iter())
+            .map(|(available_actions, q_s)| available_actions.as_ref().map_or(0, |available_actions| greedy_action(q_s, available_actions)))
+            .collect();
+        LearningResult {
+            pi,
+            q_values,
+            duration: self.start_time.elapsed(),
+            episodes: self.episode_metrics.len(),
+            steps: self.steps,
+            episode_metrics: self.episode_metrics,
+            converged: self.last_q_change < Q_CONVERGENCE_TOLERANCE,
+            average_reward: None,
+            soft_policy: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converged_when_q_stops_moving_between_checkpoints() {
+        let mut recorder = LearningRecorder::new(1);
+        recorder.end_episode(1.0, &[vec![0.5, 0.0]]);
+        recorder.end_episode(1.0, &[vec![0.5, 0.0]]);
+        assert!(recorder.finish(vec![vec![0.5, 0.0]]).converged);
+
+        let mut recorder = LearningRecorder::new(1);
+        recorder.end_episode(1.0, &[vec![0.5, 0.0]]);
+        recorder.end_episode(1.0, &[vec![0.6, 0.0]]);
+        assert!(!recorder.finish(vec![vec![0.6, 0.0]]).converged);
+
+        // Un seul point de contrôle ne permet pas de mesurer une variation
+        let mut recorder = LearningRecorder::new(1);
+        recorder.end_episode(1.0, &[vec![0.5, 0.0]]);
+        assert!(!recorder.finish(vec![vec![0.5, 0.0]]).converged);
+    }
+}
@@ -1,72 +1,69 @@
-use std::cmp::max;
-use std::time::{SystemTime, UNIX_EPOCH};
-use crate::contracts::mdp_env::MDPEnv;
+use std::time::Instant;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+use crate::algorithms::training_result::PlanningResult;
 
-pub fn value_iteration<TEnv: MDPEnv>(gamma: f32, theta: f32) -> (Vec<usize>, Vec<f32>) {
-
-    let mut pi = vec![0usize; TEnv::num_states()];
-    let mut value_function = vec![0.0; TEnv::num_states()];
-
-    let num_states = TEnv::num_states();
-    let num_actions = TEnv::num_actions();
-    let num_rewards = TEnv::num_rewards();
-    let rewards = (0..num_rewards).map(|r_index| TEnv::reward(r_index)).collect::<Vec<f32>>();
-    let num_floats = num_states * num_actions * num_states * num_rewards;
-
-    let mut cached_transition_probabilities = vec![42f32; num_floats];
+pub fn value_iteration(mdp: &impl Mdp, gamma: f32, theta: f32, max_sweeps: usize) -> PlanningResult {
+    let model = SparseTransitionModel::from_mdp(mdp);
+    value_iteration_with_model(&model, gamma, theta, max_sweeps)
+}
 
-    for s in 0..num_states {
-        let index = s * num_actions * num_states * num_rewards;
-        for a in 0..num_actions {
-            let index = index + a * num_states * num_rewards;
-            for s_p in 0..num_states {
-                let index = index + s_p * num_rewards;
-                for r_index in 0..num_rewards {
-                    let index = index + r_index;
-                    cached_transition_probabilities[index] = TEnv::transition_probability(s, a, s_p, r_index);
-                }
-            }
-        }
-    }
+// S'arrête quand un balayage bouge V de moins de `theta`, ou après `max_sweeps` balayages (`converged` faux)
+pub fn value_iteration_with_model(model: &SparseTransitionModel, gamma: f32, theta: f32, max_sweeps: usize) -> PlanningResult {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let mut pi = vec![0usize; num_states];
+    let mut value_function = vec![0.0; num_states];
 
-    let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64;
+    let start_time = Instant::now();
 
-    loop {
+    let mut sweeps = 0;
+    let mut converged = false;
+    while sweeps < max_sweeps {
         let mut delta = 0.0f32;
         for s in 0..num_states {
             let v = value_function[s];
-            let mut actions = vec![0.0; num_actions];
-            for a in 0..num_actions{
-                let mut total = 0f32;
-                let s_index = s * num_actions * num_states * num_rewards;
-                for s_p in 0..num_states {
-                    let s_p_index = s_index + a * num_states * num_rewards + s_p * num_rewards;
-                    for r_index in 0..num_rewards {
-                        let index = s_p_index + r_index;
-                        total += cached_transition_probabilities[index] * (rewards[r_index] + gamma * value_function[s_p]);
-                    }
-                }
-                actions[a] = total;
-            }
 
-            value_function[s] = actions[0];
-            let mut j = 0;
-            for i in actions{
-                if i > value_function[s] {
-                    pi[s] = j;
-                    value_function[s] = i;
-                }
-                j += 1;
-            }
+            // En cas d'égalité on garde la première action
+            let (best_action, best_value) = (0..num_actions)
+                .map(|a| (a, model.q_value(s, a, &value_function, gamma)))
+                .fold((0, f32::NEG_INFINITY), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+            pi[s] = best_action;
+            value_function[s] = best_value;
 
             delta = delta.max((v - value_function[s]).abs());
         }
+        sweeps += 1;
         if delta < theta {
+            converged = true;
             break;
         }
     }
-    println!("time : {}", SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as f64 - start_time);
-    (pi, value_function)
+    PlanningResult {
+        pi,
+        value_function,
+        duration: start_time.elapsed(),
+        iterations: sweeps,
+        converged,
+        average_reward: None,
+        policy_iteration_stats: None,
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn reports_whether_the_sweep_limit_was_reached() {
+        let model = SparseTransitionModel::from_mdp_env::<LineWorld<5>>();
+        let converged = value_iteration_with_model(&model, 0.999, 0.001, 10_000);
+        assert!(converged.converged);
+        assert!(converged.iterations > 1);
 
+        let truncated = value_iteration_with_model(&model, 0.999, 0.001, 1);
+        assert!(!truncated.converged);
+        assert_eq!(truncated.iterations, 1);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::contracts::environment::{Environment, Transition};
+use crate::algorithms::training_result::{LearningRecorder, LearningResult};
+use crate::algorithms::epsilon_greedy::{epsilon_greedy_action, greedy_action};
+use crate::algorithms::eligibility_traces::{EligibilityTraces, TraceKind};
+use rand::Rng;
+
+// Q(lambda) de Watkins : les traces sont coupées dès que l'action de comportement n'est pas greedy
+#[allow(clippy::too_many_arguments)]
+pub fn watkins_q_lambda(
+    env: &mut impl Environment,
+    num_episodes: usize,
+    learning_rate: f32,
+    gamma: f32,
+    lambda: f32,
+    epsilon: f32,
+    trace_kind: TraceKind,
+    rng: &mut impl Rng,
+) -> LearningResult {
+    let mut q_values = vec![vec![0.0; env.num_actions()]; env.num_states()];
+    let mut traces = EligibilityTraces::new(trace_kind, env.num_states(), env.num_actions());
+
+    let mut recorder = LearningRecorder::new(env.num_states());
+
+    for _ in 0..num_episodes {
+        env.reset();
+        traces.clear();
+        if env.is_game_over() {
+            recorder.end_episode(env.score(), &q_values);
+            continue;
+        }
+        let mut s = env.state_id();
+        let mut available_actions = env.available_actions();
+        let mut a = epsilon_greedy_action(&q_values[s], &available_actions, epsilon, rng);
+        while !env.is_game_over() {
+            recorder.record_step(s, &available_actions);
+            let Transition { reward: r, next_state: s_p, terminated, .. } = env.step(a);
+
+            traces.visit(s, a, learning_rate);
+
+            if terminated {
+                let delta = r - q_values[s][a];
+                traces.update(&mut q_values, learning_rate, delta);
+                break;
+            }
+
+            available_actions = env.available_actions();
+            let a_p = epsilon_greedy_action(&q_values[s_p], &available_actions, epsilon, rng);
+            // En cas d'égalité, l'action choisie compte comme greedy
+            let greedy = greedy_action(&q_values[s_p], &available_actions);
+            let a_star = if q_values[s_p][a_p] == q_values[s_p][greedy] { a_p } else { greedy };
+            let delta = r + gamma * q_values[s_p][a_star] - q_values[s][a];
+            traces.update(&mut q_values, learning_rate, delta);
+            if a_p == a_star {
+                traces.decay(gamma * lambda);
+            } else {
+                traces.clear();
+            }
+            s = s_p;
+            a = a_p;
+        }
+        recorder.end_episode(env.score(), &q_values);
+    }
+
+    recorder.finish(q_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand::RngCore;
+
+    // 0 --(action 0)--> 1, puis depuis 1 : action 0 -> fin avec +1, action 1 -> fin avec -1.
+    // Garde les actions jouées en 1 pour rejouer l'apprentissage à la main.
+    struct Fork {
+        state: usize,
+        score: f32,
+        actions_in_1: Vec<usize>,
+    }
+
+    impl Environment for Fork {
+        fn num_states(&self) -> usize {
+            3
+        }
+
+        fn num_actions(&self) -> usize {
+            2
+        }
+
+        fn reset(&mut self) {
+            self.state = 0;
+            self.score = 0.0;
+        }
+
+        fn reset_to_random_state(&mut self, _rng: &mut dyn RngCore) {
+            self.reset();
+        }
+
+        fn is_game_over(&self) -> bool {
+            self.state == 2
+        }
+
+        fn score(&self) -> f32 {
+            self.score
+        }
+
+        fn state_id(&self) -> usize {
+            self.state
+        }
+
+        fn is_forbidden(&self, action: usize) -> bool {
+            !self.available_actions().contains(&action)
+        }
+
+        fn available_actions(&self) -> Vec<usize> {
+            match self.state {
+                0 => vec![0],
+                1 => vec![0, 1],
+                _ => vec![],
+            }
+        }
+
+        fn step(&mut self, action: usize) -> Transition {
+            let reward = match self.state {
+                0 => 0.0,
+                _ => {
+                    self.actions_in_1.push(action);
+                    if action == 0 { 1.0 } else { -1.0 }
+                }
+            };
+            self.state += 1;
+            self.score += reward;
+            Transition { reward, next_state: self.state, terminated: self.state == 2, truncated: false }
+        }
+    }
+
+    #[test]
+    fn traces_are_cut_after_an_exploratory_action() {
+        let learning_rate = 0.5;
+        let mut env = Fork { state: 0, score: 0.0, actions_in_1: vec![] };
+        let result = watkins_q_lambda(&mut env, 30, learning_rate, 1.0, 1.0, 0.5, TraceKind::Accumulating, &mut StdRng::seed_from_u64(0));
+
+        // Rejoue les mêmes actions avec lambda = gamma = 1 : Q(0, 0) ne reçoit l'erreur du dernier pas
+        // que si l'action jouée en 1 était greedy
+        let replay = |cut_traces: bool| {
+            let (mut q_0, mut q_1, mut cuts) = (0.0f32, [0.0f32; 2], 0);
+            for &action in env.actions_in_1.iter() {
+                let best = q_1[0].max(q_1[1]);
+                q_0 += learning_rate * (best - q_0);
+                let exploratory = q_1[action] != best;
+                cuts += exploratory as usize;
+                let reward = if action == 0 { 1.0 } else { -1.0 };
+                let delta = reward - q_1[action];
+                if !(cut_traces && exploratory) {
+                    q_0 += learning_rate * delta;
+                }
+                q_1[action] += learning_rate * delta;
+            }
+            (q_0, q_1, cuts)
+        };
+
+        let (q_0, q_1, cuts) = replay(true);
+        assert!(cuts > 0, "no exploratory action in the run");
+        assert!((result.q_values[0][0] - q_0).abs() < 1e-5, "{} vs {}", result.q_values[0][0], q_0);
+        assert!((result.q_values[1][0] - q_1[0]).abs() < 1e-5 && (result.q_values[1][1] - q_1[1]).abs() < 1e-5);
+        assert!((result.q_values[0][0] - replay(false).0).abs() > 1e-3);
+    }
+}
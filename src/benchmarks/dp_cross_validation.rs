@@ -0,0 +1,65 @@
+use crate::algorithms::linear_programming::{linear_programming_with_model, LinearProgramError};
+use crate::algorithms::policy_iteration::{modified_policy_iteration_with_model, ValueUpdate};
+use crate::algorithms::value_iteration::value_iteration_with_model;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+const MAX_SWEEPS: usize = 100_000;
+const POLICY_VALUE_TOLERANCE: f32 = 1e-3;
+
+// Résout le même modèle par value iteration, policy iteration et programmation linéaire.
+// Renvoie (écart max |V_vi - V_lp|, écart max |V_pi - V_lp|, nombre d'états où les politiques ne sont pas équivalentes).
+// Chaque algorithme départage les ex aequo à sa façon (murs, états terminaux...) : un état ne compte comme désaccord
+// que si l'action choisie par VI ou PI vaut, sous V_lp, moins que celle de la programmation linéaire.
+pub fn dp_cross_validation(model: &SparseTransitionModel, gamma: f32, theta: f32) -> Result<(f32, f32, usize), LinearProgramError> {
+    let vi = value_iteration_with_model(model, gamma, theta, MAX_SWEEPS);
+    let pi = modified_policy_iteration_with_model(model, gamma, theta, None, ValueUpdate::GaussSeidel, MAX_SWEEPS);
+    let lp = linear_programming_with_model(model, gamma)?;
+
+    let max_difference = |v: &[f32]| v.iter().zip(lp.value_function.iter()).map(|(v1, v2)| (v1 - v2).abs()).fold(0.0, f32::max);
+    let q_value = |s: usize, a: usize| model.q_value(s, a, &lp.value_function, gamma);
+    let policy_disagreements = (0..model.num_states())
+        .filter(|&s| {
+            let best = q_value(s, lp.pi[s]);
+            best - q_value(s, vi.pi[s]) > POLICY_VALUE_TOLERANCE || best - q_value(s, pi.pi[s]) > POLICY_VALUE_TOLERANCE
+        })
+        .count();
+    Ok((max_difference(&vi.value_function), max_difference(&pi.value_function), policy_disagreements))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::envs::map_grid_world::MapGridWorld;
+    use crate::models::random_mdp::random_mdp;
+
+    fn assert_agreement(model: &SparseTransitionModel) {
+        let (vi_error, pi_error, policy_disagreements) = dp_cross_validation(model, 0.9, 0.00001).unwrap();
+        assert!(vi_error < 1e-3, "max |V_vi - V_lp| = {}", vi_error);
+        assert!(pi_error < 1e-3, "max |V_pi - V_lp| = {}", pi_error);
+        assert_eq!(policy_disagreements, 0);
+        // Un V identiquement nul rendrait la comparaison triviale
+        let lp = linear_programming_with_model(model, 0.9).unwrap();
+        assert!(lp.value_function.iter().any(|v| v.abs() > 0.1), "V = {:?}", lp.value_function);
+    }
+
+    #[test]
+    fn linear_programming_agrees_with_dynamic_programming_on_random_mdps() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5 {
+            assert_agreement(&random_mdp(20, 3, 4, 5, &mut rng));
+        }
+    }
+
+    #[test]
+    fn linear_programming_agrees_with_dynamic_programming_on_map_grid_world() {
+        let map_grid_world = MapGridWorld::from_map("
+            S..#.
+            .#...
+            ...#X
+            .#..G
+        ").unwrap();
+        assert_agreement(&SparseTransitionModel::from_mdp(&map_grid_world));
+    }
+}
@@ -0,0 +1,23 @@
+use crate::envs::maximization_bias::{STATE_A, STATE_B};
+
+// Mesure du biais de maximisation sur `num_runs` exécutions d'un algorithme :
+// (Q(A, gauche) moyen, max_a Q(B, a) moyen, proportion d'exécutions dont la politique greedy va à gauche depuis A).
+// Sans biais, les deux premières valeurs tendent vers B_REWARD_MEAN et la proportion vers 0.
+pub fn maximization_bias<const NB_B_ACTIONS: usize>(
+    mut train: impl FnMut() -> Vec<Vec<f32>>,
+    num_runs: usize,
+) -> (f32, f32, f32) {
+    let mut q_a_left = 0.0;
+    let mut max_q_b = 0.0;
+    let mut left_count = 0;
+    for _ in 0..num_runs {
+        let q_values = train();
+        q_a_left += q_values[STATE_A][0];
+        max_q_b += q_values[STATE_B][..NB_B_ACTIONS].iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        if q_values[STATE_A][0] > q_values[STATE_A][1] {
+            left_count += 1;
+        }
+    }
+    (q_a_left / num_runs as f32, max_q_b / num_runs as f32, left_count as f32 / num_runs as f32)
+}
+
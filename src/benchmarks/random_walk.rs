@@ -0,0 +1,50 @@
+use rand::Rng;
+use crate::algorithms::n_step_sarsa::n_step_sarsa;
+use crate::contracts::environment::Environment;
+
+// Valeurs exactes de Q sous la politique aléatoire uniforme, sans actualisation, sur LineWorld :
+// depuis la case s (non terminale), le retour attendu vaut 2s / (NB_CELLS - 1) - 1.
+// Les états terminaux ont une ligne vide.
+pub fn random_policy_q_values<const NB_CELLS: usize>() -> Vec<Vec<f32>> {
+    let value = |s: usize| 2.0 * s as f32 / (NB_CELLS - 1) as f32 - 1.0;
+    (0..NB_CELLS).map(|s| {
+        if s == 0 || s == NB_CELLS - 1 {
+            vec![]
+        } else {
+            vec![value(s - 1), value(s + 1)]
+        }
+    }).collect()
+}
+
+// Erreur quadratique moyenne de SARSA à n pas sous politique aléatoire sur `env`, par rapport à `true_q_values`
+// (sur les paires (s, a) présentes, les états terminaux ayant une ligne vide),
+// moyennée sur `num_runs` exécutions de `num_episodes` épisodes.
+// Reproduit les courbes biais/variance de la marche aléatoire en faisant varier n et le pas d'apprentissage.
+pub fn n_step_sarsa_random_walk_errors(
+    env: &mut impl Environment,
+    true_q_values: &[Vec<f32>],
+    ns: &[usize],
+    learning_rates: &[f32],
+    num_episodes: usize,
+    num_runs: usize,
+    rng: &mut impl Rng,
+) -> Vec<(usize, f32, f32)> {
+    let num_pairs = true_q_values.iter().map(Vec::len).sum::<usize>();
+    let mut errors = vec![];
+    for &n in ns {
+        for &learning_rate in learning_rates {
+            let mut total = 0.0;
+            for _ in 0..num_runs {
+                let q_values = n_step_sarsa(env, num_episodes, n, learning_rate, 1.0, 1.0, rng).q_values;
+                let squared_error = true_q_values.iter()
+                    .enumerate()
+                    .flat_map(|(s, true_q_s)| true_q_s.iter().enumerate().map(move |(a, true_q)| (s, a, true_q)))
+                    .map(|(s, a, true_q)| (q_values[s][a] - true_q).powi(2))
+                    .sum::<f32>();
+                total += (squared_error / num_pairs as f32).sqrt();
+            }
+            errors.push((n, learning_rate, total / num_runs as f32));
+        }
+    }
+    errors
+}
@@ -0,0 +1,64 @@
+use crate::algorithms::epsilon_greedy::greedy_action;
+use crate::algorithms::training_result::LearningResult;
+use crate::contracts::environment::Environment;
+
+// Déroule la politique greedy de `q_values` depuis l'état initial de `env`, sur au plus `max_steps` pas.
+// Renvoie la longueur de l'épisode et le score final s'il se termine.
+pub fn greedy_rollout(env: &mut impl Environment, q_values: &[Vec<f32>], max_steps: usize) -> Option<(usize, f32)> {
+    env.reset();
+    for steps in 0..=max_steps {
+        if env.is_game_over() {
+            return Some((steps, env.score()));
+        }
+        if steps == max_steps {
+            break;
+        }
+        env.step(greedy_action(&q_values[env.state_id()], &env.available_actions()));
+    }
+    None
+}
+
+// Entraîne sur `env` avec des budgets d'épisodes croissants jusqu'à ce que la politique greedy atteigne
+// `optimal_score` en au plus `optimal_length` pas. Renvoie (épisodes, pas réels consommés) pour ce budget,
+// les pas étant ceux comptés par `LearningResult::steps` (hors pas de planification).
+pub fn steps_to_optimal_policy<TEnv: Environment>(
+    env: &mut TEnv,
+    mut train: impl FnMut(&mut TEnv, usize) -> LearningResult,
+    episode_budgets: &[usize],
+    optimal_length: usize,
+    optimal_score: f32,
+) -> Option<(usize, usize)> {
+    for &num_episodes in episode_budgets {
+        let LearningResult { q_values, steps, .. } = train(env, num_episodes);
+        if let Some((length, score)) = greedy_rollout(env, &q_values, optimal_length) {
+            if length <= optimal_length && score >= optimal_score {
+                return Some((num_episodes, steps));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::algorithms::q_learning::q_learning;
+    use crate::envs::map_grid_world::MapGridWorld;
+
+    #[test]
+    fn works_on_an_environment_configured_at_runtime() {
+        let mut env = MapGridWorld::from_map("S..\n.#.\n..G").unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let (num_episodes, steps) = steps_to_optimal_policy(
+            &mut env,
+            |env, num_episodes| q_learning(env, num_episodes, 0.5, 0.9, 1.0, &mut rng),
+            &[1, 10, 100, 1_000],
+            4,
+            1.0,
+        ).unwrap();
+        // Chaque épisode fait au moins les 4 pas du plus court chemin
+        assert!(steps >= 4 * num_episodes);
+    }
+}
@@ -0,0 +1,170 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use crate::contracts::env_error::EnvError;
+use crate::contracts::mdp::StaticMdp;
+use crate::contracts::model_free_env::ModelFreeEnv;
+use crate::envs::grid_world::GridWorld;
+use crate::envs::line_world::LineWorld;
+use crate::envs::secret::{set_secret_env_path, SecretEnv0, SecretEnv1, SecretEnv2, SecretEnv3, SecretEnvWrapper, SecretLibraryEnv};
+use crate::models::consistency_check::{check_consistency, ConsistencyReport};
+use crate::models::mdp_comparison::compare_mdps;
+use crate::models::model_file::load_model;
+use crate::models::validation::{validate_mdp, validate_model, ModelValidationReport};
+
+const DEFAULT_TOLERANCE: f32 = 1e-4;
+const DEFAULT_ROLLOUTS: usize = 10_000;
+const CONSISTENCY_MAX_STEPS: usize = 1_000;
+const CONSISTENCY_MIN_SAMPLES: usize = 30;
+const CONSISTENCY_SIGNIFICANCE: f64 = 1e-3;
+
+const USAGE: &str = "Usage: [--secret-env-lib <library>] <command>
+  validate <env> [tolerance]           check the MDP model of line_world, grid_world or secret_env_0..3
+  validate --model <file> [tolerance]  check an MDP model saved with save_model
+  consistency <env> [rollouts] [seed]  compare rollouts of the env against its declared MDP model
+  compare-libs <id> <library> <library> [tolerance]
+                                       compare the MDP model of secret_env_<id> in two versions of the library
+The secret-env library is --secret-env-lib, else $SECRET_ENV_PATH, else ./libs/ in the current directory";
+
+// Sous-commandes en ligne de commande. Renvoie le code de sortie, ou None sans sous-commande
+// (le programme lance alors les démonstrations de `main`).
+pub fn run(args: &[String]) -> Option<i32> {
+    // L'option globale vaut aussi pour les démonstrations
+    let args = match args {
+        [flag, path, rest @ ..] if flag == "--secret-env-lib" => {
+            set_secret_env_path(path);
+            rest
+        }
+        _ => args,
+    };
+    let command = args.first()?;
+    let code = match command.as_str() {
+        "validate" => validate(&args[1..]),
+        "consistency" => consistency(&args[1..]),
+        "compare-libs" => compare_libs(&args[1..]),
+        _ => {
+            eprintln!("Unknown command: {}\n{}", command, USAGE);
+            2
+        }
+    };
+    Some(code)
+}
+
+fn validate(args: &[String]) -> i32 {
+    let report = match args {
+        [flag, path, rest @ ..] if flag == "--model" => match load_model(path) {
+            Ok(model) => validate_model(&model, parse_tolerance(rest), None),
+            Err(error) => {
+                eprintln!("Failed to load model {}: {}", path, error);
+                return 1;
+            }
+        },
+        [env, rest @ ..] => {
+            if let Err(error) = load_secret_env(env) {
+                eprintln!("{}", error);
+                return 1;
+            }
+            match validate_env(env, parse_tolerance(rest)) {
+                Some(report) => report,
+                None => {
+                    eprintln!("Unknown environment: {}\n{}", env, USAGE);
+                    return 2;
+                }
+            }
+        }
+        [] => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+    println!("{}", report);
+    if report.is_valid() { 0 } else { 1 }
+}
+
+fn parse_tolerance(args: &[String]) -> f32 {
+    args.first().and_then(|tolerance| tolerance.parse().ok()).unwrap_or(DEFAULT_TOLERANCE)
+}
+
+// L'accessibilité est vérifiée depuis l'état initial de `new()`
+fn validate_env(env: &str, tolerance: f32) -> Option<ModelValidationReport> {
+    Some(match env {
+        "line_world" => validate_mdp(&StaticMdp::<LineWorld<5>>::new(), tolerance, Some(LineWorld::<5>::new().state_id())),
+        "grid_world" => validate_mdp(&StaticMdp::<GridWorld<4, 4>>::new(), tolerance, Some(GridWorld::<4, 4>::new().state_id())),
+        "secret_env_0" => validate_mdp(&StaticMdp::<SecretEnv0>::new(), tolerance, Some(SecretEnv0::new().state_id())),
+        "secret_env_1" => validate_mdp(&StaticMdp::<SecretEnv1>::new(), tolerance, Some(SecretEnv1::new().state_id())),
+        "secret_env_2" => validate_mdp(&StaticMdp::<SecretEnv2>::new(), tolerance, Some(SecretEnv2::new().state_id())),
+        "secret_env_3" => validate_mdp(&StaticMdp::<SecretEnv3>::new(), tolerance, Some(SecretEnv3::new().state_id())),
+        _ => return None,
+    })
+}
+
+// Charge à l'avance un environnement secret pour afficher une erreur lisible si la bibliothèque ou un symbole
+// manque, au lieu de paniquer au premier appel. Sans effet pour les autres environnements.
+fn load_secret_env(env: &str) -> Result<(), EnvError> {
+    let secret_env_id = match env {
+        "secret_env_0" => 0,
+        "secret_env_1" => 1,
+        "secret_env_2" => 2,
+        "secret_env_3" => 3,
+        _ => return Ok(()),
+    };
+    SecretEnvWrapper::try_cached(secret_env_id).map(|_| ())
+}
+
+fn consistency(args: &[String]) -> i32 {
+    let Some(env) = args.first() else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+    if let Err(error) = load_secret_env(env) {
+        eprintln!("{}", error);
+        return 1;
+    }
+    let num_rollouts = args.get(1).and_then(|rollouts| rollouts.parse().ok()).unwrap_or(DEFAULT_ROLLOUTS);
+    // Sans graine, les déroulés changent à chaque exécution
+    let mut rng = match args.get(2).and_then(|seed| seed.parse().ok()) {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let Some(report) = check_env_consistency(env, num_rollouts, &mut rng) else {
+        eprintln!("Unknown environment: {}\n{}", env, USAGE);
+        return 2;
+    };
+    println!("{}", report);
+    if report.is_consistent() { 0 } else { 1 }
+}
+
+fn check_env_consistency(env: &str, num_rollouts: usize, rng: &mut StdRng) -> Option<ConsistencyReport> {
+    let (max_steps, min_samples, significance) = (CONSISTENCY_MAX_STEPS, CONSISTENCY_MIN_SAMPLES, CONSISTENCY_SIGNIFICANCE);
+    Some(match env {
+        "line_world" => check_consistency(&mut LineWorld::<5>::new(), num_rollouts, max_steps, min_samples, significance, rng),
+        "grid_world" => check_consistency(&mut GridWorld::<4, 4>::new(), num_rollouts, max_steps, min_samples, significance, rng),
+        "secret_env_0" => check_consistency(&mut SecretEnv0::new(), num_rollouts, max_steps, min_samples, significance, rng),
+        "secret_env_1" => check_consistency(&mut SecretEnv1::new(), num_rollouts, max_steps, min_samples, significance, rng),
+        "secret_env_2" => check_consistency(&mut SecretEnv2::new(), num_rollouts, max_steps, min_samples, significance, rng),
+        "secret_env_3" => check_consistency(&mut SecretEnv3::new(), num_rollouts, max_steps, min_samples, significance, rng),
+        _ => return None,
+    })
+}
+
+fn compare_libs(args: &[String]) -> i32 {
+    let [secret_env_id, library1, library2, rest @ ..] = args else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+    let Ok(secret_env_id) = secret_env_id.parse::<u8>() else {
+        eprintln!("Invalid secret env id: {}\n{}", secret_env_id, USAGE);
+        return 2;
+    };
+    let envs = SecretLibraryEnv::from_library(library1, secret_env_id)
+        .and_then(|env1| Ok((env1, SecretLibraryEnv::from_library(library2, secret_env_id)?)));
+    let (env1, env2) = match envs {
+        Ok(envs) => envs,
+        Err(error) => {
+            eprintln!("{}", error);
+            return 1;
+        }
+    };
+    let report = compare_mdps(&env1, &env2, parse_tolerance(rest));
+    println!("{}", report);
+    if report.is_identical() { 0 } else { 1 }
+}
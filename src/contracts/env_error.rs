@@ -0,0 +1,26 @@
+use std::fmt;
+
+// Erreurs des environnements et du chargement des environnements secrets, pour qu'un harnais puisse
+// les journaliser et continuer au lieu de voir le processus s'arrêter
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnvError {
+    // Action valide mais interdite dans l'état courant (ou partie terminée)
+    ForbiddenAction { state: usize, action: usize },
+    // Indice d'action hors de 0..num_actions
+    InvalidAction { action: usize, num_actions: usize },
+    LibraryNotFound { path: String, message: String },
+    SymbolMissing { symbol: String, message: String },
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvError::ForbiddenAction { state, action } => write!(f, "Forbidden action {} in state {}", action, state),
+            EnvError::InvalidAction { action, num_actions } => write!(f, "Invalid action {} (the environment has {} actions)", action, num_actions),
+            EnvError::LibraryNotFound { path, message } => write!(f, "Failed to load library {}: {}", path, message),
+            EnvError::SymbolMissing { symbol, message } => write!(f, "Failed to load symbol {}: {}", symbol, message),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
@@ -0,0 +1,108 @@
+use rand::RngCore;
+use crate::contracts::env_error::EnvError;
+use crate::contracts::model_free_env::ModelFreeEnv;
+
+// Résultat d'un pas. `terminated` : l'état atteint est terminal, sa valeur est nulle.
+// `truncated` : l'épisode est interrompu sans être terminé (par exemple une limite de pas), il faut alors
+// continuer d'estimer la valeur de `next_state`. `is_game_over` est vrai dans les deux cas.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub reward: f32,
+    pub next_state: usize,
+    pub terminated: bool,
+    pub truncated: bool,
+}
+
+// Environnement sans modèle dont les dimensions sont celles de l'instance, ce qui permet des environnements
+// configurés à l'exécution. Tout ModelFreeEnv en est un : `reset_to_random_state` remplace `from_random_state`
+// et la récompense d'un pas est la variation de `score()` (un ModelFreeEnv n'est jamais tronqué).
+pub trait Environment {
+    fn num_states(&self) -> usize;
+    fn num_actions(&self) -> usize;
+    fn reset(&mut self);
+    fn reset_to_random_state(&mut self, rng: &mut dyn RngCore);
+    fn is_game_over(&self) -> bool;
+    fn score(&self) -> f32;
+    fn state_id(&self) -> usize;
+    fn is_forbidden(&self, action: usize) -> bool;
+    fn available_actions(&self) -> Vec<usize>;
+    fn step(&mut self, action: usize) -> Transition;
+
+    // Variante de `step` qui renvoie une erreur au lieu d'arrêter le programme sur une action
+    // hors de 0..num_actions, interdite, ou jouée alors que la partie est terminée
+    fn try_step(&mut self, action: usize) -> Result<Transition, EnvError> {
+        if action >= self.num_actions() {
+            return Err(EnvError::InvalidAction { action, num_actions: self.num_actions() });
+        }
+        if self.is_game_over() || self.is_forbidden(action) {
+            return Err(EnvError::ForbiddenAction { state: self.state_id(), action });
+        }
+        Ok(self.step(action))
+    }
+}
+
+impl<TEnv: ModelFreeEnv> Environment for TEnv {
+    fn num_states(&self) -> usize {
+        TEnv::num_states()
+    }
+
+    fn num_actions(&self) -> usize {
+        TEnv::num_actions()
+    }
+
+    fn reset(&mut self) {
+        ModelFreeEnv::reset(self)
+    }
+
+    fn reset_to_random_state(&mut self, mut rng: &mut dyn RngCore) {
+        *self = TEnv::from_random_state(&mut rng);
+    }
+
+    fn is_game_over(&self) -> bool {
+        ModelFreeEnv::is_game_over(self)
+    }
+
+    fn score(&self) -> f32 {
+        ModelFreeEnv::score(self)
+    }
+
+    fn state_id(&self) -> usize {
+        ModelFreeEnv::state_id(self)
+    }
+
+    fn is_forbidden(&self, action: usize) -> bool {
+        ModelFreeEnv::is_forbidden(self, action)
+    }
+
+    fn available_actions(&self) -> Vec<usize> {
+        ModelFreeEnv::available_actions(self)
+    }
+
+    fn step(&mut self, action: usize) -> Transition {
+        let previous_score = ModelFreeEnv::score(self);
+        ModelFreeEnv::step(self, action);
+        Transition {
+            reward: ModelFreeEnv::score(self) - previous_score,
+            next_state: ModelFreeEnv::state_id(self),
+            terminated: ModelFreeEnv::is_game_over(self),
+            truncated: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn try_step_rejects_invalid_and_forbidden_actions() {
+        let mut line_world = LineWorld::<5>::new();
+        assert_eq!(line_world.try_step(5), Err(EnvError::InvalidAction { action: 5, num_actions: 2 }));
+        assert_eq!(line_world.try_step(0), Ok(Transition { reward: 0.0, next_state: 1, terminated: false, truncated: false }));
+        assert_eq!(line_world.try_step(0), Ok(Transition { reward: -1.0, next_state: 0, terminated: true, truncated: false }));
+        // Sur la case terminale 0, même une action valide est refusée, sans arrêter le programme
+        assert_eq!(line_world.try_step(1), Err(EnvError::ForbiddenAction { state: 0, action: 1 }));
+        assert_eq!(Environment::state_id(&line_world), 0);
+    }
+}
@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+use crate::contracts::mdp_env::MDPEnv;
+
+// Modèle d'un MDP interrogé sur une instance, ce qui permet des tailles et des dynamiques connues seulement à l'exécution.
+// Tout MDPEnv en est un (quelle que soit l'instance, les réponses sont celles des fonctions associées).
+pub trait Mdp {
+    fn num_states(&self) -> usize;
+    fn num_actions(&self) -> usize;
+    fn num_rewards(&self) -> usize;
+    fn reward(&self, index: usize) -> f32;
+    fn transition_probability(&self, state: usize, action: usize, next_state: usize, reward_index: usize) -> f32;
+
+    // Comme `MDPEnv::nonzero_transitions` : balayage complet par défaut, à redéfinir quand les transitions sont connues
+    fn nonzero_transitions(&self, state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        let mut transitions = vec![];
+        for s_p in 0..self.num_states() {
+            for r_index in 0..self.num_rewards() {
+                let probability = self.transition_probability(state, action, s_p, r_index);
+                if probability != 0.0 {
+                    transitions.push((s_p, r_index, probability));
+                }
+            }
+        }
+        transitions
+    }
+}
+
+impl<TEnv: MDPEnv> Mdp for TEnv {
+    fn num_states(&self) -> usize {
+        TEnv::num_states()
+    }
+
+    fn num_actions(&self) -> usize {
+        TEnv::num_actions()
+    }
+
+    fn num_rewards(&self) -> usize {
+        TEnv::num_rewards()
+    }
+
+    fn reward(&self, index: usize) -> f32 {
+        TEnv::reward(index)
+    }
+
+    fn transition_probability(&self, state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+        TEnv::transition_probability(state, action, next_state, reward_index)
+    }
+
+    fn nonzero_transitions(&self, state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        TEnv::nonzero_transitions(state, action)
+    }
+}
+
+// Instance vide d'un MDPEnv, pour l'utiliser comme Mdp sans en construire une vraie
+// (pour SecretEnv, sans créer d'instance côté bibliothèque ; elle peut aussi être partagée entre threads).
+pub struct StaticMdp<TEnv: MDPEnv>(PhantomData<fn() -> TEnv>);
+
+impl<TEnv: MDPEnv> StaticMdp<TEnv> {
+    pub fn new() -> Self {
+        StaticMdp(PhantomData)
+    }
+}
+
+impl<TEnv: MDPEnv> Default for StaticMdp<TEnv> {
+    fn default() -> Self {
+        StaticMdp::new()
+    }
+}
+
+impl<TEnv: MDPEnv> MDPEnv for StaticMdp<TEnv> {
+    fn num_states() -> usize {
+        TEnv::num_states()
+    }
+
+    fn num_actions() -> usize {
+        TEnv::num_actions()
+    }
+
+    fn num_rewards() -> usize {
+        TEnv::num_rewards()
+    }
+
+    fn reward(index: usize) -> f32 {
+        TEnv::reward(index)
+    }
+
+    fn transition_probability(state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+        TEnv::transition_probability(state, action, next_state, reward_index)
+    }
+
+    fn nonzero_transitions(state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        TEnv::nonzero_transitions(state, action)
+    }
+}
@@ -4,4 +4,20 @@ pub trait MDPEnv {
     fn num_rewards() -> usize;
     fn reward(index: usize) -> f32;
     fn transition_probability(state: usize, action: usize, next_state: usize, reward_index: usize) -> f32;
+
+    // (s', indice de récompense, probabilité) de probabilité non nulle depuis (state, action).
+    // Par défaut, interroge `transition_probability` pour chaque (s', r) ; un environnement qui connaît
+    // ses transitions doit la redéfinir pour que la construction du modèle ne dépende que des entrées non nulles.
+    fn nonzero_transitions(state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        let mut transitions = vec![];
+        for s_p in 0..Self::num_states() {
+            for r_index in 0..Self::num_rewards() {
+                let probability = Self::transition_probability(state, action, s_p, r_index);
+                if probability != 0.0 {
+                    transitions.push((s_p, r_index, probability));
+                }
+            }
+        }
+        transitions
+    }
 }
\ No newline at end of file
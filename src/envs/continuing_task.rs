@@ -0,0 +1,112 @@
+use rand::RngCore;
+use crate::contracts::environment::{Environment, Transition};
+
+// Transforme un environnement épisodique en tâche continue : quand l'épisode se termine, l'environnement
+// repart aussitôt de son état initial. L'état terminal n'est donc jamais observé, la transition qui y menait
+// arrive directement sur l'état initial avec la même récompense, et le score cumule celui des épisodes terminés.
+// `SparseTransitionModel::to_continuing` applique la même transformation à un modèle.
+pub struct ContinuingTask<TEnv: Environment> {
+    env: TEnv,
+    finished_episodes_score: f32,
+}
+
+impl<TEnv: Environment> ContinuingTask<TEnv> {
+    pub fn new(env: TEnv) -> Self {
+        let mut continuing_task = ContinuingTask {
+            env,
+            finished_episodes_score: 0.0,
+        };
+        continuing_task.restart_if_over();
+        continuing_task
+    }
+
+    fn restart_if_over(&mut self) {
+        if self.env.is_game_over() {
+            self.finished_episodes_score += self.env.score();
+            self.env.reset();
+        }
+    }
+}
+
+impl<TEnv: Environment> Environment for ContinuingTask<TEnv> {
+    fn num_states(&self) -> usize {
+        self.env.num_states()
+    }
+
+    fn num_actions(&self) -> usize {
+        self.env.num_actions()
+    }
+
+    fn reset(&mut self) {
+        self.env.reset();
+        self.finished_episodes_score = 0.0;
+        self.restart_if_over();
+    }
+
+    fn reset_to_random_state(&mut self, rng: &mut dyn RngCore) {
+        self.env.reset_to_random_state(rng);
+        self.finished_episodes_score = 0.0;
+        self.restart_if_over();
+    }
+
+    fn is_game_over(&self) -> bool {
+        false
+    }
+
+    fn score(&self) -> f32 {
+        self.finished_episodes_score + self.env.score()
+    }
+
+    fn state_id(&self) -> usize {
+        self.env.state_id()
+    }
+
+    fn is_forbidden(&self, action: usize) -> bool {
+        self.env.is_forbidden(action)
+    }
+
+    fn available_actions(&self) -> Vec<usize> {
+        self.env.available_actions()
+    }
+
+    fn step(&mut self, action: usize) -> Transition {
+        let Transition { reward, .. } = self.env.step(action);
+        self.restart_if_over();
+        Transition {
+            reward,
+            next_state: self.env.state_id(),
+            terminated: false,
+            truncated: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+    use crate::envs::map_grid_world::MapGridWorld;
+
+    #[test]
+    fn restarts_line_world_without_ending() {
+        let mut continuing_task = ContinuingTask::new(LineWorld::<5>::new());
+        for _ in 0..2 {
+            assert_eq!(continuing_task.step(1), Transition { reward: 0.0, next_state: 3, terminated: false, truncated: false });
+            // La transition vers l'état terminal 4 arrive directement sur l'état initial
+            assert_eq!(continuing_task.step(1), Transition { reward: 1.0, next_state: 2, terminated: false, truncated: false });
+        }
+        assert_eq!(continuing_task.score(), 2.0);
+    }
+
+    #[test]
+    fn wraps_a_map_grid_world() {
+        let mut continuing_task = ContinuingTask::new(MapGridWorld::from_map("SG").unwrap());
+        for _ in 0..3 {
+            // right
+            assert_eq!(continuing_task.step(3), Transition { reward: 1.0, next_state: 0, terminated: false, truncated: false });
+        }
+        assert_eq!(continuing_task.score(), 3.0);
+        assert!(!continuing_task.is_game_over());
+    }
+}
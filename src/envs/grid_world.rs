@@ -1,5 +1,6 @@
 use rand::Rng;
 use crate::contracts::mdp_env::MDPEnv;
+use crate::contracts::env_error::EnvError;
 use crate::contracts::model_free_env::ModelFreeEnv;
 
 pub struct GridWorld<const ROWS: usize, const COLUMNS: usize> {
@@ -89,6 +90,23 @@ impl<const ROWS: usize, const COLUMNS: usize> MDPEnv for GridWorld<ROWS, COLUMNS
 
         0.0 // Retourne 0 pour toutes les autres transitions non valides
     }
+
+    fn nonzero_transitions(state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        // Mêmes cas que `transition_probability` : chaque action autorisée mène à une seule case,
+        // avec une probabilité par indice de récompense
+        if state == 0 || state == (ROWS - 1) * COLUMNS + (COLUMNS - 1) {
+            return vec![];
+        }
+        let (row, col) = (state / COLUMNS, state % COLUMNS);
+        let (next_state, probabilities) = match action {
+            0 if row > 0 => (state - COLUMNS, [0.5, 0.7, 0.9]),
+            1 if row < ROWS - 1 => (state + COLUMNS, [0.6, 0.8, 0.95]),
+            2 if col > 0 => (state - 1, [0.4, 0.75, 0.85]),
+            3 if col < COLUMNS - 1 => (state + 1, [0.3, 0.7, 0.9]),
+            _ => return vec![],
+        };
+        probabilities.iter().enumerate().map(|(r_index, &probability)| (next_state, r_index, probability)).collect()
+    }
 }
 
 impl<const ROWS: usize, const COLUMNS: usize> ModelFreeEnv for GridWorld<ROWS, COLUMNS> {
@@ -168,9 +186,7 @@ impl<const ROWS: usize, const COLUMNS: usize> ModelFreeEnv for GridWorld<ROWS, C
 
     fn step(&mut self, action: usize) {
         if self.is_forbidden(action) {
-            eprintln!("Forbidden action: {:?}", action);  // Affiche l'action interdite
-            eprintln!("Current position: ({}, {})", self.current_row, self.current_column);
-            std::process::exit(42);
+            panic!("{}", EnvError::ForbiddenAction { state: self.state_id(), action });
         }
 
         match action {
@@ -1,5 +1,6 @@
 use rand::Rng;
 use crate::contracts::mdp_env::MDPEnv;
+use crate::contracts::env_error::EnvError;
 use crate::contracts::model_free_env::ModelFreeEnv;
 
 pub struct LineWorld<const NB_CELLS: usize> {
@@ -39,6 +40,20 @@ impl<const NB_CELLS: usize> MDPEnv for LineWorld<NB_CELLS> {
             _ => 0.0,
         }
     }
+
+    fn nonzero_transitions(state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        // Mêmes cas que `transition_probability`, une seule issue par (s, a) non terminal
+        if state == 0 || state == NB_CELLS - 1 {
+            return vec![];
+        }
+        match action {
+            0 if state == 1 => vec![(0, 0, 1.0)],
+            0 => vec![(state - 1, 1, 1.0)],
+            1 if state == NB_CELLS - 2 => vec![(state + 1, 2, 1.0)],
+            1 => vec![(state + 1, 1, 1.0)],
+            _ => vec![],
+        }
+    }
 }
 
 impl<const NB_CELLS: usize> ModelFreeEnv for LineWorld<NB_CELLS> {
@@ -85,7 +100,7 @@ impl<const NB_CELLS: usize> ModelFreeEnv for LineWorld<NB_CELLS> {
     }
 
     fn is_forbidden(&self, action: usize) -> bool {
-        self.is_game_over() && !(0..=1).contains(&action)
+        self.is_game_over() || !(0..=1).contains(&action)
     }
 
     fn available_actions(&self) -> Vec<usize> {
@@ -98,8 +113,7 @@ impl<const NB_CELLS: usize> ModelFreeEnv for LineWorld<NB_CELLS> {
 
     fn step(&mut self, action: usize) {
         if self.is_forbidden(action) {
-            eprintln!("Forbidden action");
-            std::process::exit(42);
+            panic!("{}", EnvError::ForbiddenAction { state: self.current_cell, action });
         }
 
         match action {
@@ -108,4 +122,20 @@ impl<const NB_CELLS: usize> ModelFreeEnv for LineWorld<NB_CELLS> {
             _ => panic!("Invalid action, should not happen"),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_action_is_forbidden_once_the_game_is_over() {
+        for terminal_cell in [0, 4] {
+            let env = LineWorld::<5> { current_cell: terminal_cell };
+            assert!(env.is_forbidden(0) && env.is_forbidden(1));
+        }
+        let env = LineWorld::<5>::new();
+        assert!(!env.is_forbidden(0) && !env.is_forbidden(1));
+        assert!(env.is_forbidden(2));
+    }
+}
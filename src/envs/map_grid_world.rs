@@ -0,0 +1,209 @@
+use rand::seq::IteratorRandom;
+use rand::RngCore;
+use crate::contracts::env_error::EnvError;
+use crate::contracts::environment::{Environment, Transition};
+use crate::contracts::mdp::Mdp;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cell {
+    Floor,
+    Wall,
+    Goal,
+    Trap,
+}
+
+// Grille lue à l'exécution depuis une carte texte, une ligne par rangée :
+// '.' case libre, '#' mur, 'S' départ, 'G' sortie (+1), 'X' piège (-1).
+// Déplacements déterministes (up, down, left, right) ; sortir de la grille ou entrer dans un mur est interdit.
+// Les cases 'G' et 'X' terminent l'épisode. Implémente directement Mdp et Environment, sans paramètre de type.
+#[derive(Clone, Debug)]
+pub struct MapGridWorld {
+    cells: Vec<Cell>,
+    rows: usize,
+    columns: usize,
+    start: usize,
+    current: usize,
+    score: f32,
+}
+
+impl MapGridWorld {
+    pub fn from_map_file(path: &str) -> Result<Self, String> {
+        let map = std::fs::read_to_string(path).map_err(|error| format!("cannot read {}: {}", path, error))?;
+        MapGridWorld::from_map(&map)
+    }
+
+    pub fn from_map(map: &str) -> Result<Self, String> {
+        let lines = map.lines().map(str::trim).filter(|line| !line.is_empty()).collect::<Vec<&str>>();
+        let rows = lines.len();
+        let columns = lines.first().map_or(0, |line| line.chars().count());
+        if rows == 0 || columns == 0 {
+            return Err("empty map".to_string());
+        }
+
+        let mut cells = Vec::with_capacity(rows * columns);
+        let mut start = None;
+        for (row, line) in lines.iter().enumerate() {
+            if line.chars().count() != columns {
+                return Err(format!("row {} has {} cells instead of {}", row, line.chars().count(), columns));
+            }
+            for symbol in line.chars() {
+                let cell = match symbol {
+                    '.' => Cell::Floor,
+                    '#' => Cell::Wall,
+                    'G' => Cell::Goal,
+                    'X' => Cell::Trap,
+                    'S' => {
+                        if start.replace(cells.len()).is_some() {
+                            return Err("several start cells 'S'".to_string());
+                        }
+                        Cell::Floor
+                    }
+                    _ => return Err(format!("unknown cell '{}' in row {}", symbol, row)),
+                };
+                cells.push(cell);
+            }
+        }
+        let start = start.ok_or("no start cell 'S'")?;
+
+        Ok(MapGridWorld {
+            cells,
+            rows,
+            columns,
+            start,
+            current: start,
+            score: 0.0,
+        })
+    }
+
+    fn is_terminal(&self, state: usize) -> bool {
+        matches!(self.cells[state], Cell::Goal | Cell::Trap)
+    }
+
+    fn cell_reward(&self, state: usize) -> f32 {
+        match self.cells[state] {
+            Cell::Goal => 1.0,
+            Cell::Trap => -1.0,
+            _ => 0.0,
+        }
+    }
+
+    // Case atteinte depuis `state` avec `action`, ou None si le déplacement est interdit
+    fn target(&self, state: usize, action: usize) -> Option<usize> {
+        let (row, column) = (state / self.columns, state % self.columns);
+        let next_state = match action {
+            0 if row > 0 => state - self.columns, // up
+            1 if row < self.rows - 1 => state + self.columns, // down
+            2 if column > 0 => state - 1, // left
+            3 if column < self.columns - 1 => state + 1, // right
+            _ => return None,
+        };
+        (self.cells[next_state] != Cell::Wall).then_some(next_state)
+    }
+}
+
+impl Mdp for MapGridWorld {
+    fn num_states(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    fn num_actions(&self) -> usize {
+        4 // up, down, left, right
+    }
+
+    fn num_rewards(&self) -> usize {
+        3 // -1, 0, +1
+    }
+
+    fn reward(&self, index: usize) -> f32 {
+        match index {
+            0 => -1.0, // Piège
+            1 => 0.0,  // Case libre
+            2 => 1.0,  // Sortie
+            _ => panic!("Invalid reward index"),
+        }
+    }
+
+    fn transition_probability(&self, state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+        // Pas de transition depuis un mur ou un état terminal
+        if self.cells[state] == Cell::Wall || self.is_terminal(state) {
+            return 0.0;
+        }
+        match self.target(state, action) {
+            Some(target) if target == next_state && Mdp::reward(self, reward_index) == self.cell_reward(target) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn nonzero_transitions(&self, state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        if self.cells[state] == Cell::Wall || self.is_terminal(state) {
+            return vec![];
+        }
+        // Indices de `reward` : piège 0, case libre 1, sortie 2
+        self.target(state, action)
+            .map(|target| {
+                let r_index = match self.cells[target] {
+                    Cell::Trap => 0,
+                    Cell::Goal => 2,
+                    _ => 1,
+                };
+                (target, r_index, 1.0)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Environment for MapGridWorld {
+    fn num_states(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    fn num_actions(&self) -> usize {
+        4 // up, down, left, right
+    }
+
+    fn reset(&mut self) {
+        self.current = self.start;
+        self.score = 0.0;
+    }
+
+    fn reset_to_random_state(&mut self, rng: &mut dyn RngCore) {
+        self.current = (0..self.cells.len()).filter(|&s| self.cells[s] != Cell::Wall).choose(rng).unwrap();
+        self.score = 0.0;
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.is_terminal(self.current)
+    }
+
+    fn score(&self) -> f32 {
+        self.score
+    }
+
+    fn state_id(&self) -> usize {
+        self.current
+    }
+
+    fn is_forbidden(&self, action: usize) -> bool {
+        self.target(self.current, action).is_none()
+    }
+
+    fn available_actions(&self) -> Vec<usize> {
+        (0..4).filter(|&action| !self.is_forbidden(action)).collect()
+    }
+
+    fn step(&mut self, action: usize) -> Transition {
+        let Some(next_state) = self.target(self.current, action) else {
+            panic!("{}", EnvError::ForbiddenAction { state: self.current, action });
+        };
+        let reward = self.cell_reward(next_state);
+        self.current = next_state;
+        self.score += reward;
+        Transition {
+            reward,
+            next_state,
+            terminated: self.is_terminal(next_state),
+            truncated: false,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::contracts::env_error::EnvError;
+use crate::contracts::model_free_env::ModelFreeEnv;
+
+// MDP classique du biais de maximisation (Sutton & Barto, exemple 6.7).
+// Depuis A (état 0) : action 0 "gauche" vers B sans récompense, action 1 "droite" vers la fin sans récompense.
+// Depuis B (état 1) : NB_B_ACTIONS actions, toutes vers la fin avec une récompense tirée selon N(-0.1, 1).
+// Aller à gauche est donc sous-optimal, mais max_a Q(B, a) surestime B tant que les estimations sont bruitées.
+// Le bruit des récompenses de B vient d'un générateur propre à l'instance, conservé par `reset` :
+// `from_rng` le dérive du générateur de l'expérience pour qu'une seule graine contrôle toute l'exécution.
+pub struct MaximizationBias<const NB_B_ACTIONS: usize> {
+    current_state: usize,
+    score: f32,
+    noise_rng: StdRng,
+}
+
+pub const STATE_A: usize = 0;
+pub const STATE_B: usize = 1;
+pub const STATE_TERMINAL: usize = 2;
+
+pub const B_REWARD_MEAN: f32 = -0.1;
+
+impl<const NB_B_ACTIONS: usize> MaximizationBias<NB_B_ACTIONS> {
+    pub fn from_rng(rng: &mut impl Rng) -> Self {
+        MaximizationBias {
+            current_state: STATE_A,
+            score: 0.0,
+            noise_rng: StdRng::seed_from_u64(rng.gen()),
+        }
+    }
+}
+
+impl<const NB_B_ACTIONS: usize> ModelFreeEnv for MaximizationBias<NB_B_ACTIONS> {
+    fn new() -> Self {
+        MaximizationBias {
+            current_state: STATE_A,
+            score: 0.0,
+            noise_rng: StdRng::from_entropy(),
+        }
+    }
+
+    fn from_random_state(rng: &mut impl Rng) -> Self {
+        MaximizationBias {
+            current_state: rng.gen_range(STATE_A..=STATE_B),
+            score: 0.0,
+            noise_rng: StdRng::seed_from_u64(rng.gen()),
+        }
+    }
+
+    fn num_states() -> usize {
+        3
+    }
+
+    fn num_actions() -> usize {
+        NB_B_ACTIONS.max(2)
+    }
+
+    fn reset(&mut self) {
+        self.current_state = STATE_A;
+        self.score = 0.0;
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.current_state == STATE_TERMINAL
+    }
+
+    fn score(&self) -> f32 {
+        self.score
+    }
+
+    fn state_id(&self) -> usize {
+        self.current_state
+    }
+
+    fn is_forbidden(&self, action: usize) -> bool {
+        !self.available_actions().contains(&action)
+    }
+
+    fn available_actions(&self) -> Vec<usize> {
+        match self.current_state {
+            STATE_A => vec![0, 1],
+            STATE_B => (0..NB_B_ACTIONS).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn step(&mut self, action: usize) {
+        if self.is_forbidden(action) {
+            panic!("{}", EnvError::ForbiddenAction { state: self.current_state, action });
+        }
+
+        match (self.current_state, action) {
+            (STATE_A, 0) => self.current_state = STATE_B,
+            (STATE_A, 1) => self.current_state = STATE_TERMINAL,
+            (STATE_B, _) => {
+                // Box-Muller pour tirer selon N(B_REWARD_MEAN, 1)
+                let (u1, u2) = (1.0 - self.noise_rng.gen::<f32>(), self.noise_rng.gen::<f32>());
+                let noise = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos();
+                self.score += B_REWARD_MEAN + noise;
+                self.current_state = STATE_TERMINAL;
+            }
+            _ => panic!("Invalid action, should not happen"),
+        }
+    }
+}
@@ -1,6 +1,11 @@
 mod paths;
 mod secret_env;
 mod secret_env_wrapper;
+mod secret_library_env;
+
+pub use paths::{secret_env_path, set_secret_env_path, SECRET_ENV_PATH_VAR};
+pub use secret_env_wrapper::SecretEnvWrapper;
+pub use secret_library_env::SecretLibraryEnv;
 
 pub type SecretEnv0 = secret_env::SecretEnv<0>;
 pub type SecretEnv1 = secret_env::SecretEnv<1>;
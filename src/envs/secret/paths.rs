@@ -1,8 +1,30 @@
+use std::sync::RwLock;
+
 #[cfg(target_os = "linux")]
-pub const SECRET_ENV_PATH: &str = "./libs/libsecret_envs.so";
+pub const DEFAULT_SECRET_ENV_PATH: &str = "./libs/libsecret_envs.so";
 #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-pub const SECRET_ENV_PATH: &str = "./libs/libsecret_envs_intel_macos.dylib";
+pub const DEFAULT_SECRET_ENV_PATH: &str = "./libs/libsecret_envs_intel_macos.dylib";
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-pub const SECRET_ENV_PATH: &str = "./libs/libsecret_envs.dylib";
+pub const DEFAULT_SECRET_ENV_PATH: &str = "./libs/libsecret_envs.dylib";
 #[cfg(windows)]
-pub const SECRET_ENV_PATH: &str = "./libs/secret_envs.dll";
\ No newline at end of file
+pub const DEFAULT_SECRET_ENV_PATH: &str = "./libs/secret_envs.dll";
+
+// Variable d'environnement donnant le chemin de la bibliothèque des environnements secrets
+pub const SECRET_ENV_PATH_VAR: &str = "SECRET_ENV_PATH";
+
+// Chemin fixé à l'exécution (option --secret-env-lib), commun à tous les threads
+static SECRET_ENV_PATH_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+// À appeler avant d'utiliser SecretEnv<ID> : chaque thread garde la bibliothèque résolue à sa première utilisation
+pub fn set_secret_env_path(path: &str) {
+    *SECRET_ENV_PATH_OVERRIDE.write().unwrap() = Some(path.to_string());
+}
+
+// Bibliothèque utilisée par SecretEnv<ID> : celle fixée par `set_secret_env_path`, sinon celle de
+// la variable SECRET_ENV_PATH, sinon le chemin par défaut (relatif au dossier courant)
+pub fn secret_env_path() -> String {
+    if let Some(path) = SECRET_ENV_PATH_OVERRIDE.read().unwrap().as_ref() {
+        return path.clone();
+    }
+    std::env::var(SECRET_ENV_PATH_VAR).unwrap_or_else(|_| DEFAULT_SECRET_ENV_PATH.to_string())
+}
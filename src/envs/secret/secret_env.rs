@@ -1,3 +1,4 @@
+use crate::contracts::env_error::EnvError;
 use crate::contracts::mdp_env::MDPEnv;
 use crate::contracts::model_free_env::ModelFreeEnv;
 use crate::envs::secret::secret_env_wrapper::SecretEnvWrapper;
@@ -9,31 +10,43 @@ pub struct SecretEnv<const ENV_ID: u8> {
     instance: *mut c_void,
 }
 
+impl<const ENV_ID: u8> SecretEnv<ENV_ID> {
+    // Comme `new`, mais renvoie l'erreur si la bibliothèque ou un symbole manque au lieu de paniquer
+    pub fn try_new() -> Result<Self, EnvError> {
+        let wrapper = SecretEnvWrapper::try_cached(ENV_ID)?;
+        let instance = (wrapper.new_fn)();
+        Ok(SecretEnv {
+            wrapper,
+            instance,
+        })
+    }
+}
+
 impl<const ENV_ID: u8> MDPEnv for SecretEnv<ENV_ID> {
     fn num_states() -> usize {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).num_states_fn)()
+        (SecretEnvWrapper::cached(ENV_ID).num_states_fn)()
     }
 
     fn num_actions() -> usize {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).num_actions_fn)()
+        (SecretEnvWrapper::cached(ENV_ID).num_actions_fn)()
     }
 
     fn num_rewards() -> usize {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).num_rewards_fn)()
+        (SecretEnvWrapper::cached(ENV_ID).num_rewards_fn)()
     }
 
     fn reward(index: usize) -> f32 {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).reward_fn)(index)
+        (SecretEnvWrapper::cached(ENV_ID).reward_fn)(index)
     }
 
     fn transition_probability(state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).transition_probability_fn)(state, action, next_state, reward_index)
+        (SecretEnvWrapper::cached(ENV_ID).transition_probability_fn)(state, action, next_state, reward_index)
     }
 }
 
 impl<const ENV_ID: u8> ModelFreeEnv for SecretEnv<ENV_ID> {
     fn new() -> Self {
-        let wrapper = SecretEnvWrapper::from_secret_id(ENV_ID);
+        let wrapper = SecretEnvWrapper::cached(ENV_ID);
         let instance = (wrapper.new_fn)();
         SecretEnv {
             wrapper,
@@ -42,7 +55,7 @@ impl<const ENV_ID: u8> ModelFreeEnv for SecretEnv<ENV_ID> {
     }
 
     fn from_random_state(_rng: &mut impl Rng) -> Self {
-        let wrapper = SecretEnvWrapper::from_secret_id(ENV_ID);
+        let wrapper = SecretEnvWrapper::cached(ENV_ID);
         let instance = (wrapper.from_random_state_fn)();
         SecretEnv {
             wrapper,
@@ -51,11 +64,11 @@ impl<const ENV_ID: u8> ModelFreeEnv for SecretEnv<ENV_ID> {
     }
 
     fn num_states() -> usize {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).num_states_fn)()
+        (SecretEnvWrapper::cached(ENV_ID).num_states_fn)()
     }
 
     fn num_actions() -> usize {
-        (SecretEnvWrapper::from_secret_id(ENV_ID).num_actions_fn)()
+        (SecretEnvWrapper::cached(ENV_ID).num_actions_fn)()
     }
 
     fn reset(&mut self) {
@@ -79,20 +92,7 @@ impl<const ENV_ID: u8> ModelFreeEnv for SecretEnv<ENV_ID> {
     }
 
     fn available_actions(&self) -> Vec<usize> {
-        let mut vec = Vec::new();
-        let available_actions_raw = (self.wrapper.available_actions_fn)(self.instance);
-        let available_actions_len = (self.wrapper.available_actions_len_fn)(self.instance);
-
-        for i in 0..available_actions_len {
-            vec.push(
-                unsafe {
-                    *available_actions_raw.add(i)
-                }
-            );
-        }
-
-        (self.wrapper.available_actions_delete_fn)(available_actions_raw, available_actions_len);
-        vec
+        self.wrapper.available_actions(self.instance)
     }
 
     fn step(&mut self, action: usize) {
@@ -1,4 +1,7 @@
-use crate::envs::secret::paths::SECRET_ENV_PATH;
+use crate::contracts::env_error::EnvError;
+use crate::envs::secret::paths::secret_env_path;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi::c_void;
 
 type NumStatesFn = extern fn() -> usize;
@@ -24,11 +27,20 @@ type ScoreFn = extern fn(*mut c_void) -> f32;
 type DeleteFn = extern fn(*mut c_void);
 
 thread_local! {
-    static SECRET_LIB: libloading::Library = unsafe {
-        libloading::Library::new(SECRET_ENV_PATH).expect("Failed to load library")
-    };
+    // Bibliothèques chargées, par chemin, pour pouvoir utiliser plusieurs versions côte à côte.
+    // Elles ne sont jamais déchargées, les pointeurs de fonction des wrappers restent donc valides.
+    // Une erreur de chargement est gardée pour être renvoyée à chaque tentative, sans arrêter le programme.
+    static SECRET_LIBS: RefCell<HashMap<String, Result<&'static libloading::Library, EnvError>>> = RefCell::new(HashMap::new());
+
+    // Symboles déjà résolus, par bibliothèque et identifiant d'environnement secret (un cache par thread, comme SECRET_LIBS)
+    static CACHED_WRAPPERS: RefCell<HashMap<(String, u8), SecretEnvWrapper>> = RefCell::new(HashMap::new());
+
+    // Symboles de la bibliothèque configurée, par identifiant : SecretEnv<ID> passe par ce cache à chaque appel,
+    // le chemin n'est donc résolu qu'une fois par thread et par environnement
+    static DEFAULT_WRAPPERS: RefCell<HashMap<u8, SecretEnvWrapper>> = RefCell::new(HashMap::new());
 }
 
+#[derive(Clone, Copy)]
 pub struct SecretEnvWrapper {
     pub num_states_fn: NumStatesFn,
     pub num_actions_fn: NumActionsFn,
@@ -54,96 +66,128 @@ pub struct SecretEnvWrapper {
 }
 
 impl SecretEnvWrapper {
-    pub fn from_secret_id(secret_env_id: u8) -> Self {
-        let prefix = format!("secret_env_{}", secret_env_id);
-
-        let num_states_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_num_states\0", prefix).as_bytes()).expect("Failed to load num_states"))
-        };
-
-        let num_actions_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_num_actions\0", prefix).as_bytes()).expect("Failed to load num_actions"))
-        };
-
-        let num_rewards_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_num_rewards\0", prefix).as_bytes()).expect("Failed to load num_rewards"))
-        };
-
-        let reward_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_reward\0", prefix).as_bytes()).expect("Failed to load reward"))
-        };
-
-        let transition_probability_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_transition_probability\0", prefix).as_bytes()).expect("Failed to load transition_probability"))
-        };
-
-        let new_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_new\0", prefix).as_bytes()).expect("Failed to load new"))
-        };
-
-        let from_random_state_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_from_random_state\0", prefix).as_bytes()).expect("Failed to load from_random_state"))
-        };
-
-        let reset_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_reset\0", prefix).as_bytes()).expect("Failed to load reset"))
-        };
+    // Comme `from_secret_id`, sans refaire la résolution des symboles à chaque appel
+    pub fn cached(secret_env_id: u8) -> Self {
+        DEFAULT_WRAPPERS.with(|wrappers| wrappers.borrow().get(&secret_env_id).copied())
+            .unwrap_or_else(|| SecretEnvWrapper::try_cached(secret_env_id).unwrap_or_else(|error| panic!("{}", error)))
+    }
 
-        let state_id_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_state_id\0", prefix).as_bytes()).expect("Failed to load state_id"))
-        };
+    pub fn try_cached(secret_env_id: u8) -> Result<Self, EnvError> {
+        if let Some(wrapper) = DEFAULT_WRAPPERS.with(|wrappers| wrappers.borrow().get(&secret_env_id).copied()) {
+            return Ok(wrapper);
+        }
+        let wrapper = SecretEnvWrapper::cached_from_library(&secret_env_path(), secret_env_id)?;
+        DEFAULT_WRAPPERS.with(|wrappers| wrappers.borrow_mut().insert(secret_env_id, wrapper));
+        Ok(wrapper)
+    }
 
-        let is_forbidden_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_is_forbidden\0", prefix).as_bytes()).expect("Failed to load is_forbidden"))
-        };
+    // Comme `from_library`, sans refaire la résolution des symboles à chaque appel
+    pub fn cached_from_library(path: &str, secret_env_id: u8) -> Result<Self, EnvError> {
+        let key = (path.to_string(), secret_env_id);
+        if let Some(wrapper) = CACHED_WRAPPERS.with(|wrappers| wrappers.borrow().get(&key).copied()) {
+            return Ok(wrapper);
+        }
+        let wrapper = SecretEnvWrapper::from_library(path, secret_env_id)?;
+        CACHED_WRAPPERS.with(|wrappers| wrappers.borrow_mut().insert(key, wrapper));
+        Ok(wrapper)
+    }
 
-        let is_game_over_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_is_game_over\0", prefix).as_bytes()).expect("Failed to load is_game_over"))
-        };
+    // Environnement `secret_env_id` de la bibliothèque configurée (voir `secret_env_path`)
+    pub fn from_secret_id(secret_env_id: u8) -> Self {
+        SecretEnvWrapper::try_from_secret_id(secret_env_id).unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        let available_actions_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_available_actions\0", prefix).as_bytes()).expect("Failed to load available_actions"))
-        };
+    pub fn try_from_secret_id(secret_env_id: u8) -> Result<Self, EnvError> {
+        SecretEnvWrapper::from_library(&secret_env_path(), secret_env_id)
+    }
 
-        let available_actions_len_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_available_actions_len\0", prefix).as_bytes()).expect("Failed to load available_actions_len"))
-        };
+    // Environnement `secret_env_id` de la bibliothèque `path`, chargée une seule fois par thread.
+    // Ne renvoie que la table des symboles, sans créer de partie : pour jouer, `SecretLibraryEnv::from_library`
+    // crée une instance et la libère quand elle est détruite, comme SecretEnv<ID> avec la bibliothèque configurée
+    pub fn from_library(path: &str, secret_env_id: u8) -> Result<Self, EnvError> {
+        let lib = load_library(path)?;
+        let prefix = format!("secret_env_{}", secret_env_id);
+        unsafe {
+            Ok(SecretEnvWrapper {
+                num_states_fn: load_symbol(lib, &prefix, "num_states")?,
+                num_actions_fn: load_symbol(lib, &prefix, "num_actions")?,
+                num_rewards_fn: load_symbol(lib, &prefix, "num_rewards")?,
+                reward_fn: load_symbol(lib, &prefix, "reward")?,
+                transition_probability_fn: load_symbol(lib, &prefix, "transition_probability")?,
+                new_fn: load_symbol(lib, &prefix, "new")?,
+                from_random_state_fn: load_symbol(lib, &prefix, "from_random_state")?,
+                reset_fn: load_symbol(lib, &prefix, "reset")?,
+                state_id_fn: load_symbol(lib, &prefix, "state_id")?,
+                is_forbidden_fn: load_symbol(lib, &prefix, "is_forbidden")?,
+                is_game_over_fn: load_symbol(lib, &prefix, "is_game_over")?,
+                available_actions_fn: load_symbol(lib, &prefix, "available_actions")?,
+                available_actions_len_fn: load_symbol(lib, &prefix, "available_actions_len")?,
+                available_actions_delete_fn: load_symbol(lib, &prefix, "available_actions_delete")?,
+                step_fn: load_symbol(lib, &prefix, "step")?,
+                score_fn: load_symbol(lib, &prefix, "score")?,
+                delete_fn: load_symbol(lib, &prefix, "delete")?,
+            })
+        }
+    }
 
-        let available_actions_delete_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_available_actions_delete\0", prefix).as_bytes()).expect("Failed to load available_actions_delete"))
-        };
+    // Copie le tableau alloué par la bibliothèque puis le rend à la bibliothèque
+    pub fn available_actions(&self, instance: *mut c_void) -> Vec<usize> {
+        let available_actions_raw = (self.available_actions_fn)(instance);
+        let available_actions_len = (self.available_actions_len_fn)(instance);
+        // Le pointeur peut être nul pour un tableau vide
+        let vec = if available_actions_len == 0 {
+            vec![]
+        } else {
+            unsafe { std::slice::from_raw_parts(available_actions_raw, available_actions_len) }.to_vec()
+        };
+        (self.available_actions_delete_fn)(available_actions_raw, available_actions_len);
+        vec
+    }
+}
 
-        let step_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_step\0", prefix).as_bytes()).expect("Failed to load step"))
-        };
+fn load_library(path: &str) -> Result<&'static libloading::Library, EnvError> {
+    SECRET_LIBS.with(|libs| {
+        libs.borrow_mut()
+            .entry(path.to_string())
+            .or_insert_with(|| {
+                unsafe { libloading::Library::new(path) }
+                    .map(|lib| &*Box::leak(Box::new(lib)))
+                    .map_err(|error| EnvError::LibraryNotFound { path: path.to_string(), message: error.to_string() })
+            })
+            .clone()
+    })
+}
 
-        let score_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_score\0", prefix).as_bytes()).expect("Failed to load score"))
-        };
+// Résout `<prefix>_<name>` ; `T` doit être le type de pointeur de fonction du symbole
+unsafe fn load_symbol<T: Copy>(lib: &libloading::Library, prefix: &str, name: &str) -> Result<T, EnvError> {
+    let symbol = format!("{}_{}", prefix, name);
+    lib.get::<T>(format!("{}\0", symbol).as_bytes())
+        .map(|function| *function)
+        .map_err(|error| EnvError::SymbolMissing { symbol, message: error.to_string() })
+}
 
-        let delete_fn = unsafe {
-            SECRET_LIB.with(|lib| *lib.get(format!("{}_delete\0", prefix).as_bytes()).expect("Failed to load delete"))
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::secret::paths::DEFAULT_SECRET_ENV_PATH;
+
+    #[test]
+    fn missing_library_is_an_error() {
+        for _ in 0..2 {
+            // La seconde tentative renvoie l'erreur gardée en cache
+            match SecretEnvWrapper::from_library("/nonexistent.so", 0) {
+                Err(EnvError::LibraryNotFound { path, .. }) => assert_eq!(path, "/nonexistent.so"),
+                _ => panic!("expected LibraryNotFound"),
+            }
+        }
+        assert!(matches!(SecretEnvWrapper::cached_from_library("/nonexistent.so", 0), Err(EnvError::LibraryNotFound { .. })));
+    }
 
-        SecretEnvWrapper {
-            num_states_fn,
-            num_actions_fn,
-            num_rewards_fn,
-            reward_fn,
-            transition_probability_fn,
-            new_fn,
-            from_random_state_fn,
-            reset_fn,
-            state_id_fn,
-            is_forbidden_fn,
-            is_game_over_fn,
-            available_actions_fn,
-            available_actions_len_fn,
-            available_actions_delete_fn,
-            step_fn,
-            score_fn,
-            delete_fn,
+    #[test]
+    fn missing_symbol_is_an_error() {
+        match SecretEnvWrapper::from_library(DEFAULT_SECRET_ENV_PATH, 255) {
+            Err(EnvError::SymbolMissing { symbol, .. }) => assert_eq!(symbol, "secret_env_255_num_states"),
+            _ => panic!("expected SymbolMissing"),
         }
     }
 }
-
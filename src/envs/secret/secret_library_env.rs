@@ -0,0 +1,144 @@
+use crate::contracts::env_error::EnvError;
+use crate::contracts::environment::{Environment, Transition};
+use crate::contracts::mdp::Mdp;
+use crate::envs::secret::secret_env_wrapper::SecretEnvWrapper;
+use rand::RngCore;
+use std::ffi::c_void;
+
+// Environnement secret d'une bibliothèque choisie à l'exécution. Contrairement à SecretEnv<ID>, qui utilise
+// la bibliothèque configurée, plusieurs versions de la bibliothèque peuvent servir dans le même processus.
+// `SecretEnvWrapper::from_library` ne fait que résoudre les symboles ; ce constructeur-ci possède en plus
+// l'instance de la bibliothèque (reset, step, delete), c'est donc lui qu'on utilise pour jouer.
+pub struct SecretLibraryEnv {
+    wrapper: SecretEnvWrapper,
+    instance: *mut c_void,
+}
+
+impl SecretLibraryEnv {
+    pub fn from_library(path: &str, secret_env_id: u8) -> Result<Self, EnvError> {
+        let wrapper = SecretEnvWrapper::cached_from_library(path, secret_env_id)?;
+        let instance = (wrapper.new_fn)();
+        Ok(SecretLibraryEnv {
+            wrapper,
+            instance,
+        })
+    }
+}
+
+impl Mdp for SecretLibraryEnv {
+    fn num_states(&self) -> usize {
+        (self.wrapper.num_states_fn)()
+    }
+
+    fn num_actions(&self) -> usize {
+        (self.wrapper.num_actions_fn)()
+    }
+
+    fn num_rewards(&self) -> usize {
+        (self.wrapper.num_rewards_fn)()
+    }
+
+    fn reward(&self, index: usize) -> f32 {
+        (self.wrapper.reward_fn)(index)
+    }
+
+    fn transition_probability(&self, state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+        (self.wrapper.transition_probability_fn)(state, action, next_state, reward_index)
+    }
+}
+
+impl Environment for SecretLibraryEnv {
+    fn num_states(&self) -> usize {
+        (self.wrapper.num_states_fn)()
+    }
+
+    fn num_actions(&self) -> usize {
+        (self.wrapper.num_actions_fn)()
+    }
+
+    fn reset(&mut self) {
+        (self.wrapper.reset_fn)(self.instance)
+    }
+
+    fn reset_to_random_state(&mut self, _rng: &mut dyn RngCore) {
+        // L'état de départ aléatoire est tiré par la bibliothèque
+        (self.wrapper.delete_fn)(self.instance);
+        self.instance = (self.wrapper.from_random_state_fn)();
+    }
+
+    fn is_game_over(&self) -> bool {
+        (self.wrapper.is_game_over_fn)(self.instance)
+    }
+
+    fn score(&self) -> f32 {
+        (self.wrapper.score_fn)(self.instance)
+    }
+
+    fn state_id(&self) -> usize {
+        (self.wrapper.state_id_fn)(self.instance)
+    }
+
+    fn is_forbidden(&self, action: usize) -> bool {
+        (self.wrapper.is_forbidden_fn)(self.instance, action)
+    }
+
+    fn available_actions(&self) -> Vec<usize> {
+        self.wrapper.available_actions(self.instance)
+    }
+
+    fn step(&mut self, action: usize) -> Transition {
+        let previous_score = self.score();
+        (self.wrapper.step_fn)(self.instance, action);
+        Transition {
+            reward: self.score() - previous_score,
+            next_state: self.state_id(),
+            terminated: self.is_game_over(),
+            truncated: false,
+        }
+    }
+}
+
+impl Drop for SecretLibraryEnv {
+    fn drop(&mut self) {
+        (self.wrapper.delete_fn)(self.instance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::secret::paths::DEFAULT_SECRET_ENV_PATH;
+
+    fn copied_library() -> std::path::PathBuf {
+        let file_name = std::path::Path::new(DEFAULT_SECRET_ENV_PATH).file_name().unwrap();
+        let dir = std::env::temp_dir().join(format!("secret_library_env_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(file_name);
+        std::fs::copy(DEFAULT_SECRET_ENV_PATH, &path).unwrap();
+        path
+    }
+
+    #[test]
+    fn libraries_from_different_paths_stay_independent() {
+        let path = copied_library();
+        let mut env1 = SecretLibraryEnv::from_library(DEFAULT_SECRET_ENV_PATH, 0).unwrap();
+        let mut env2 = SecretLibraryEnv::from_library(path.to_str().unwrap(), 0).unwrap();
+        // La copie est chargée comme une seconde bibliothèque, avec ses propres symboles
+        assert_ne!(env1.wrapper.step_fn as usize, env2.wrapper.step_fn as usize);
+        assert_eq!(Environment::num_states(&env1), Environment::num_states(&env2));
+
+        env1.reset();
+        env2.reset();
+        let (state2, score2) = (env2.state_id(), env2.score());
+        for _ in 0..10 {
+            if env1.is_game_over() {
+                break;
+            }
+            let action = env1.available_actions()[0];
+            env1.step(action);
+        }
+        // Jouer dans env1 ne touche pas à la partie de env2
+        assert_eq!((env2.state_id(), env2.score()), (state2, score2));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -2,23 +2,84 @@ pub mod algorithms {
     pub mod policy_iteration;
     pub mod value_iteration;
     pub mod q_learning;
+    pub mod sarsa;
+    pub mod expected_sarsa;
+    pub mod n_step_sarsa;
+    pub mod n_step_tree_backup;
+    pub mod eligibility_traces;
+    pub mod sarsa_lambda;
+    pub mod watkins_q_lambda;
+    pub mod double_q_learning;
+    pub mod epsilon_greedy;
+    pub mod dyna_q;
+    pub mod prioritized_sweeping;
     pub mod monte_carlo_exploring_starts;
+    pub mod linear_programming;
+    pub mod finite_horizon;
+    pub mod episodic_value_iteration;
+    pub mod off_policy_monte_carlo;
+    pub mod on_policy_first_visit_monte_carlo;
+    pub mod relative_value_iteration;
+    pub mod r_learning;
+    pub mod differential_q_learning;
+    pub mod training_result;
 }
 
 pub mod contracts {
     pub mod mdp_env;
     pub mod model_free_env;
+    pub mod mdp;
+    pub mod environment;
+    pub mod env_error;
 }
 
 pub mod envs {
     pub mod line_world;
     pub mod grid_world;
     pub mod secret;
+    pub mod maximization_bias;
+    pub mod continuing_task;
+    pub mod map_grid_world;
 }
 
+pub mod models {
+    pub mod sparse_transition_model;
+    pub mod model_file;
+    pub mod tabular_mdp;
+    pub mod validation;
+    pub mod consistency_check;
+    pub mod empirical_mdp;
+    pub mod random_mdp;
+    pub mod mdp_comparison;
+}
+
+pub mod benchmarks {
+    pub mod steps_to_optimal_policy;
+    pub mod random_walk;
+    pub mod maximization_bias;
+    pub mod dp_cross_validation;
+}
+
+pub mod cli;
+
+use rand::rngs::StdRng;
+use crate::contracts::model_free_env::ModelFreeEnv;
+use rand::SeedableRng;
+
+const DEMO_SEED: u64 = 42;
+
 fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
+    // Graine commune aux démonstrations : deux exécutions du programme donnent les mêmes résultats
+    let mut rng = StdRng::seed_from_u64(DEMO_SEED);
+
     println!("Policy Iteration on GridWorld(4x4)...");
-    let (pi, value_function) = algorithms::policy_iteration::policy_iteration::<envs::grid_world::GridWorld<4, 4>>(0.999, 0.001);
+    let algorithms::training_result::PlanningResult { pi, value_function, duration, .. } = algorithms::policy_iteration::policy_iteration(&envs::grid_world::GridWorld::<4, 4>::new(), 0.999, 0.001, 10_000);
+    println!("time : {:?}", duration);
     for (s, a) in pi.iter().enumerate() {
         println!("π(s={}) = {}", s, a);
     }
@@ -29,15 +90,283 @@ fn main() {
     println!();
     println!();
 
+    println!("Modified Policy Iteration (3 sweeps, Jacobi) on GridWorld(4x4)...");
+    let algorithms::training_result::PlanningResult { pi, value_function, duration, converged, policy_iteration_stats, .. } = algorithms::policy_iteration::modified_policy_iteration(&envs::grid_world::GridWorld::<4, 4>::new(), 0.999, 0.001, Some(3), algorithms::policy_iteration::ValueUpdate::Jacobi, 10_000);
+    println!("time : {:?}", duration);
+    for (s, a) in pi.iter().enumerate() {
+        println!("π(s={}) = {}", s, a);
+    }
+    println!();
+    for (s, v) in value_function.iter().enumerate() {
+        println!("V(s={}) = {}", s, v);
+    }
+    let stats = policy_iteration_stats.unwrap();
+    println!("{} evaluation sweeps, {} improvements, converged: {}", stats.evaluation_sweeps, stats.improvements, converged);
+    println!();
+    println!();
+
+    println!("Value Iteration on a GridWorld(4x4) model estimated from 20 000 random steps...");
+    let estimated_model = models::empirical_mdp::estimate_mdp(&mut envs::grid_world::GridWorld::<4, 4>::new(), 20_000, 1_000, true, models::empirical_mdp::uniform_random_policy, &mut rng);
+    let algorithms::training_result::PlanningResult { pi, value_function, duration, .. } = algorithms::value_iteration::value_iteration_with_model(&estimated_model, 0.999, 0.001, 10_000);
+    println!("time : {:?}", duration);
+    for (s, a) in pi.iter().enumerate() {
+        println!("π(s={}) = {}", s, a);
+    }
+    println!();
+    for (s, v) in value_function.iter().enumerate() {
+        println!("V(s={}) = {}", s, v);
+    }
+    println!();
+    println!();
+
+    println!("Linear Programming on LineWorld(5)...");
+    match algorithms::linear_programming::linear_programming(&envs::line_world::LineWorld::<5>::new(), 0.999) {
+        Ok(algorithms::training_result::PlanningResult { pi, value_function, duration, .. }) => {
+            println!("time : {:?}", duration);
+            for (s, a) in pi.iter().enumerate() {
+                println!("π(s={}) = {}", s, a);
+            }
+            println!();
+            for (s, v) in value_function.iter().enumerate() {
+                println!("V(s={}) = {}", s, v);
+            }
+        }
+        Err(error) => println!("{}", error),
+    }
+    println!();
+
+    println!("Value Iteration vs Policy Iteration vs Linear Programming on random MDPs(20 states, 3 actions)...");
+    for _ in 0..5 {
+        let model = models::random_mdp::random_mdp(20, 3, 4, 5, &mut rng);
+        match benchmarks::dp_cross_validation::dp_cross_validation(&model, 0.9, 0.00001) {
+            Ok((vi_error, pi_error, policy_disagreements)) => println!("max |V_vi - V_lp| = {}, max |V_pi - V_lp| = {}, {} states with different policies", vi_error, pi_error, policy_disagreements),
+            Err(error) => println!("{}", error),
+        }
+    }
+    println!();
+
+    println!("Value Iteration vs Policy Iteration vs Linear Programming on a MapGridWorld...");
+    let map_grid_world = envs::map_grid_world::MapGridWorld::from_map("
+        S..#.
+        .#...
+        ...#X
+        .#..G
+    ").unwrap();
+    match benchmarks::dp_cross_validation::dp_cross_validation(&models::sparse_transition_model::SparseTransitionModel::from_mdp(&map_grid_world), 0.9, 0.00001) {
+        Ok((vi_error, pi_error, policy_disagreements)) => println!("max |V_vi - V_lp| = {}, max |V_pi - V_lp| = {}, {} states with different policies", vi_error, pi_error, policy_disagreements),
+        Err(error) => println!("{}", error),
+    }
+    println!();
+    println!();
+
+    println!("Finite-horizon (H=3) backward induction on LineWorld(5)...");
+    let algorithms::training_result::FiniteHorizonResult { pi, value_function, duration } = algorithms::finite_horizon::finite_horizon(&envs::line_world::LineWorld::<5>::new(), 3, 1.0);
+    println!("time : {:?}", duration);
+    for (t, (pi_t, v_t)) in pi.iter().zip(value_function.iter()).enumerate() {
+        for (s, (a, v)) in pi_t.iter().zip(v_t.iter()).enumerate() {
+            println!("π(t={}, s={}) = {}, V(t={}, s={}) = {}", t, s, a, t, s, v);
+        }
+    }
+    println!();
+
+    println!("Undiscounted episodic Value Iteration on LineWorld(5)...");
+    match algorithms::episodic_value_iteration::episodic_value_iteration(&envs::line_world::LineWorld::<5>::new(), 0.001, 10_000) {
+        Ok(algorithms::training_result::PlanningResult { pi, value_function, iterations, .. }) => {
+            println!("{} sweeps", iterations);
+            for (s, (a, v)) in pi.iter().zip(value_function.iter()).enumerate() {
+                println!("π(s={}) = {}, V(s={}) = {}", s, a, s, v);
+            }
+        }
+        Err(error) => println!("{}", error),
+    }
+    println!();
+    println!();
+
+    println!("Relative Value Iteration on continuing LineWorld(5)...");
+    let line_world = envs::line_world::LineWorld::<5>::new();
+    let algorithms::training_result::PlanningResult { pi, value_function: relative_values, duration, converged, average_reward: rho, .. } = algorithms::relative_value_iteration::relative_value_iteration(&line_world, line_world.state_id(), 0.0001, 2, 10_000);
+    println!("time : {:?}", duration);
+    for (s, (a, h)) in pi.iter().zip(relative_values.iter()).enumerate() {
+        println!("π(s={}) = {}, h(s={}) = {}", s, a, s, h);
+    }
+    println!("rho = {}, converged: {}", rho.unwrap(), converged);
+    println!();
+    println!();
+
+    println!("R-Learning on continuing LineWorld(5)...");
+    let algorithms::training_result::LearningResult { pi, duration, average_reward: rho, .. } = algorithms::r_learning::r_learning(&mut envs::continuing_task::ContinuingTask::new(envs::line_world::LineWorld::<5>::new()), 100_000, 0.1, 0.01, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    println!("π = {:?}, rho = {}", pi, rho.unwrap());
+    println!();
+
+    println!("Differential Q-Learning on continuing LineWorld(5)...");
+    let algorithms::training_result::LearningResult { pi, duration, average_reward: rho, .. } = algorithms::differential_q_learning::differential_q_learning(&mut envs::continuing_task::ContinuingTask::new(envs::line_world::LineWorld::<5>::new()), 100_000, 0.1, 0.1, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    println!("π = {:?}, rho = {}", pi, rho.unwrap());
+    println!();
+
+    println!("Differential Q-Learning on a continuing MapGridWorld...");
+    let map_grid_world = envs::map_grid_world::MapGridWorld::from_map("
+        S..#.
+        .#...
+        ...#X
+        .#..G
+    ").unwrap();
+    let algorithms::training_result::LearningResult { pi, duration, average_reward: rho, .. } = algorithms::differential_q_learning::differential_q_learning(&mut envs::continuing_task::ContinuingTask::new(map_grid_world), 100_000, 0.1, 0.1, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    println!("π = {:?}, rho = {}", pi, rho.unwrap());
+    println!();
+    println!();
+
+    println!("Value Iteration and Q-Learning on a MapGridWorld read at runtime...");
+    let map_grid_world = envs::map_grid_world::MapGridWorld::from_map("
+        S..#.
+        .#...
+        ...#X
+        .#..G
+    ").unwrap();
+    let algorithms::training_result::PlanningResult { pi, duration, .. } = algorithms::value_iteration::value_iteration(&map_grid_world, 0.9, 0.0001, 10_000);
+    println!("time : {:?}", duration);
+    println!("value iteration π = {:?}", pi);
+    let algorithms::training_result::LearningResult { pi, duration, .. } = algorithms::q_learning::q_learning(&mut map_grid_world.clone(), 5_000, 0.1, 0.9, 0.3, &mut rng);
+    println!("time : {:?}", duration);
+    println!("q-learning π = {:?}", pi);
+    println!();
+
+    println!("Recoverable errors on forbidden and invalid actions in GridWorld(4x4)...");
+    let mut grid_world = envs::grid_world::GridWorld::<4, 4>::new();
+    for action in [0, 7, 1] {
+        match contracts::environment::Environment::try_step(&mut grid_world, action) {
+            Ok(transition) => println!("action {} : {:?}", action, transition),
+            Err(error) => println!("action {} : {}, skipped", action, error),
+        }
+    }
+    println!();
+
     println!("Q-Learning on GridWorld(4x4)...");
-    let q_values = algorithms::q_learning::q_learning::<envs::grid_world::GridWorld<4, 4>>(10_000, 0.1, 0.999, 1.0);
+    let algorithms::training_result::LearningResult { q_values, duration, episode_metrics, .. } = algorithms::q_learning::q_learning(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.1, 0.999, 1.0, &mut rng);
+    println!("time : {:?}", duration);
+    let last_episodes = &episode_metrics[episode_metrics.len().saturating_sub(100)..];
+    println!(
+        "last {} episodes : mean score {}, mean length {}",
+        last_episodes.len(),
+        last_episodes.iter().fold(0.0, |total, metrics| total + metrics.score) / last_episodes.len() as f32,
+        last_episodes.iter().map(|metrics| metrics.length).sum::<usize>() as f32 / last_episodes.len() as f32,
+    );
     for (s, q_s) in q_values.iter().enumerate() {
         for (a, q) in q_s.iter().enumerate() {
             println!("Q(s={}, a={}) = {}", s, a, q);
         }
     }
+    println!("SARSA on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::sarsa::sarsa(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.1, 0.999, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+    println!("Expected SARSA on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::expected_sarsa::expected_sarsa(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.1, 0.999, 0.1, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+    println!("Dyna-Q on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::dyna_q::dyna_q(&mut envs::grid_world::GridWorld::<4, 4>::new(), 1_000, 0.1, 0.999, 0.1, 10, algorithms::dyna_q::DynaVariant::DynaQ, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+    println!("Prioritized Sweeping vs Q-Learning on GridWorld(10x10), env steps to reach the optimal policy...");
+    let episode_budgets = [10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000];
+    let prioritized_sweeping_steps = benchmarks::steps_to_optimal_policy::steps_to_optimal_policy(
+        &mut envs::grid_world::GridWorld::<10, 10>::new(),
+        |env, num_episodes| algorithms::prioritized_sweeping::prioritized_sweeping(env, num_episodes, 0.5, 0.999, 1.0, 10, 0.0001, &mut rng),
+        &episode_budgets, 9, 1.0,
+    );
+    let q_learning_steps = benchmarks::steps_to_optimal_policy::steps_to_optimal_policy(
+        &mut envs::grid_world::GridWorld::<10, 10>::new(),
+        |env, num_episodes| algorithms::q_learning::q_learning(env, num_episodes, 0.5, 0.999, 1.0, &mut rng),
+        &episode_budgets, 9, 1.0,
+    );
+    println!("Prioritized Sweeping: {:?} (episodes, env steps)", prioritized_sweeping_steps);
+    println!("Q-Learning: {:?} (episodes, env steps)", q_learning_steps);
+    println!();
+
+    println!("n-step SARSA on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::n_step_sarsa::n_step_sarsa(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 4, 0.1, 0.999, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+
+    println!("n-step Tree Backup on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::n_step_tree_backup::n_step_tree_backup(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 4, 0.1, 0.999, 0.3, 0.0, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+
+    println!("SARSA(λ) with replacing traces on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::sarsa_lambda::sarsa_lambda(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.1, 0.999, 0.9, 0.1, algorithms::eligibility_traces::TraceKind::Replacing, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+
+    println!("Watkins Q(λ) with dutch traces on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::watkins_q_lambda::watkins_q_lambda(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.1, 0.999, 0.9, 0.3, algorithms::eligibility_traces::TraceKind::Dutch, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+
+    println!("n-step SARSA random walk on LineWorld(21), RMS error after 10 episodes...");
+    let errors = benchmarks::random_walk::n_step_sarsa_random_walk_errors(
+        &mut envs::line_world::LineWorld::<21>::new(), &benchmarks::random_walk::random_policy_q_values::<21>(),
+        &[1, 2, 4, 8, 16, usize::MAX], &[0.1, 0.2, 0.4, 0.8], 10, 20, &mut rng,
+    );
+    for (n, learning_rate, error) in errors {
+        println!("n={}, alpha={} : RMS = {}", n, learning_rate, error);
+    }
+    println!();
+
+    println!("Q-Learning vs Double Q-Learning on MaximizationBias(10 actions in B), 300 episodes x 100 runs...");
+    let (q_a_left, max_q_b, left_ratio) = benchmarks::maximization_bias::maximization_bias::<10>(
+        || {
+            let mut env = envs::maximization_bias::MaximizationBias::<10>::from_rng(&mut rng);
+            algorithms::q_learning::q_learning(&mut env, 300, 0.1, 1.0, 0.1, &mut rng).q_values
+        },
+        100,
+    );
+    println!("Q-Learning: Q(A, left) = {}, max Q(B, .) = {}, greedy left from A in {}% of runs", q_a_left, max_q_b, left_ratio * 100.0);
+    let (q_a_left, max_q_b, left_ratio) = benchmarks::maximization_bias::maximization_bias::<10>(
+        || {
+            let mut env = envs::maximization_bias::MaximizationBias::<10>::from_rng(&mut rng);
+            algorithms::double_q_learning::double_q_learning(&mut env, 300, 0.1, 1.0, 0.1, &mut rng).q_values
+        },
+        100,
+    );
+    println!("Double Q-Learning: Q(A, left) = {}, max Q(B, .) = {}, greedy left from A in {}% of runs", q_a_left, max_q_b, left_ratio * 100.0);
+    println!("True value: Q(A, left) = max Q(B, .) = {}", envs::maximization_bias::B_REWARD_MEAN);
+    println!();
+
     println!("Monte Carlo Exploring Starts on GridWorld(4x4)...");
-    let q_values = algorithms::monte_carlo_exploring_starts::monte_carlo_exploring_starts::<envs::grid_world::GridWorld<4, 4>>(15_000, 0.999);
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::monte_carlo_exploring_starts::monte_carlo_exploring_starts(&mut envs::grid_world::GridWorld::<4, 4>::new(), 15_000, 0.999, &mut rng);
+    println!("time : {:?}", duration);
     for (s, q_s) in q_values.iter().enumerate() {
         for (a, q) in q_s.iter().enumerate() {
             println!("Q(s={}, a={}) = {}", s, a, q);
@@ -54,8 +383,39 @@ fn main() {
     }
     println!();
 
+    println!("Off-policy Monte Carlo (weighted importance sampling) on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { pi, q_values, duration, .. } = algorithms::off_policy_monte_carlo::off_policy_monte_carlo(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.999, 0.3, algorithms::off_policy_monte_carlo::ImportanceSampling::Weighted, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+    println!();
+    for (s, a) in pi.iter().enumerate() {
+        println!("π(s={}) = {}", s, a);
+    }
+    println!();
+
+    println!("On-policy first-visit Monte Carlo on GridWorld(4x4)...");
+    let algorithms::training_result::LearningResult { q_values, duration, soft_policy, .. } = algorithms::on_policy_first_visit_monte_carlo::on_policy_first_visit_monte_carlo(&mut envs::grid_world::GridWorld::<4, 4>::new(), 10_000, 0.999, 0.1, &mut rng);
+    println!("time : {:?}", duration);
+    for (s, q_s) in q_values.iter().enumerate() {
+        for (a, q) in q_s.iter().enumerate() {
+            println!("Q(s={}, a={}) = {}", s, a, q);
+        }
+    }
+    println!();
+    for (s, pi_s) in soft_policy.unwrap().iter().enumerate() {
+        for (a, p) in pi_s.iter().enumerate() {
+            println!("π(s={}, a={}) = {}", s, a, p);
+        }
+    }
+    println!();
+
     println!("Monte Carlo Exploring Starts on LineWorld(5)...");
-    let q_values = algorithms::monte_carlo_exploring_starts::monte_carlo_exploring_starts::<envs::line_world::LineWorld<5>>(10_000, 0.999);
+    let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::monte_carlo_exploring_starts::monte_carlo_exploring_starts(&mut envs::line_world::LineWorld::<5>::new(), 10_000, 0.999, &mut rng);
+    println!("time : {:?}", duration);
     for (s, q_s) in q_values.iter().enumerate() {
         for (a, q) in q_s.iter().enumerate() {
             println!("Q(s={}, a={}) = {}", s, a, q);
@@ -72,7 +432,8 @@ fn main() {
     }
     println!();
     // println!("Policy Iteration on LineWorld(5)...");
-    // let (pi, value_function) = algorithms::policy_iteration::policy_iteration::<envs::line_world::LineWorld<5>>(0.999, 0.001);
+    // let algorithms::training_result::PlanningResult { pi, value_function, duration, .. } = algorithms::policy_iteration::policy_iteration(&envs::line_world::LineWorld::<5>::new(), 0.999, 0.001, 10_000);
+    // println!("time : {:?}", duration);
     // for (s, a) in pi.iter().enumerate() {
     //     println!("π(s={}) = {}", s, a);
     // }
@@ -87,7 +448,8 @@ fn main() {
 
 
     // println!("Q-Learning on LineWorld(5)...");
-    // let q_values = algorithms::q_learning::q_learning::<envs::line_world::LineWorld<5>>(10_000, 0.1, 0.999, 1.0);
+    // let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::q_learning::q_learning(&mut envs::line_world::LineWorld::<5>::new(), 10_000, 0.1, 0.999, 1.0, &mut rng);
+    // println!("time : {:?}", duration);
     // for (s, q_s) in q_values.iter().enumerate() {
     //     for (a, q) in q_s.iter().enumerate() {
     //         println!("Q(s={}, a={}) = {}", s, a, q);
@@ -95,8 +457,32 @@ fn main() {
     // }
 
     //Be careful, this will take a long time to run if you uncomment this
+    //(modified_policy_iteration with Some(k) evaluation sweeps cuts most of the evaluation time)
     // println!("Policy Iteration on SecretEnv0... (be careful, it will be very long as is)");
-    // let (pi, value_function) = algorithms::policy_iteration::policy_iteration::<envs::secret::SecretEnv0>(0.999, 0.001);
+    // let algorithms::training_result::PlanningResult { pi, value_function, duration, .. } = algorithms::policy_iteration::policy_iteration(&envs::secret::SecretEnv0::new(), 0.999, 0.001, 10_000);
+    // println!("time : {:?}", duration);
+    // println!("π(s=0) = {}", pi[0]);
+    // println!();
+    // println!("V(s=0) = {}", value_function[0]);
+    // println!();
+    // println!();
+
+    // println!("Value Iteration on SecretEnv0 with a model extracted on all cores...");
+    // let num_threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    // let model = models::sparse_transition_model::SparseTransitionModel::from_mdp_env_parallel::<envs::secret::SecretEnv0>(num_threads);
+    // let algorithms::training_result::PlanningResult { pi, value_function, duration, .. } = algorithms::value_iteration::value_iteration_with_model(&model, 0.999, 0.001, 10_000);
+    // println!("time : {:?}", duration);
+    // println!("π(s=0) = {}", pi[0]);
+    // println!();
+    // println!("V(s=0) = {}", value_function[0]);
+    // println!();
+    // println!();
+
+    // println!("Saving the SecretEnv0 model and solving the reloaded copy...");
+    // models::model_file::save_model(&model, "secret_env_0.mdp").expect("Failed to save model");
+    // models::tabular_mdp::TabularMdp::<0>::load("secret_env_0.mdp").expect("Failed to load model");
+    // let algorithms::training_result::PlanningResult { pi, value_function, duration, .. } = algorithms::value_iteration::value_iteration_with_model(&models::tabular_mdp::TabularMdp::<0>::model(), 0.999, 0.001, 10_000);
+    // println!("time : {:?}", duration);
     // println!("π(s=0) = {}", pi[0]);
     // println!();
     // println!("V(s=0) = {}", value_function[0]);
@@ -104,25 +490,29 @@ fn main() {
     // println!();
 
     // println!("Q-Learning on SecretEnv0...");
-    // let q_values = algorithms::q_learning::q_learning::<envs::secret::SecretEnv0>(10_000, 0.1, 0.999, 1.0);
+    // let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::q_learning::q_learning(&mut envs::secret::SecretEnv0::new(), 10_000, 0.1, 0.999, 1.0, &mut rng);
+    // println!("time : {:?}", duration);
     // for (a, q) in q_values[0].iter().enumerate() {
     //     println!("Q(s=0, a={}) = {}", a, q);
     // }
     //
     // println!("Q-Learning on SecretEnv1...");
-    // let q_values = algorithms::q_learning::q_learning::<envs::secret::SecretEnv1>(10_000, 0.1, 0.999, 1.0);
+    // let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::q_learning::q_learning(&mut envs::secret::SecretEnv1::new(), 10_000, 0.1, 0.999, 1.0, &mut rng);
+    // println!("time : {:?}", duration);
     // for (a, q) in q_values[0].iter().enumerate() {
     //     println!("Q(s=0, a={}) = {}", a, q);
     // }
     //
     // println!("Q-Learning on SecretEnv2...");
-    // let q_values = algorithms::q_learning::q_learning::<envs::secret::SecretEnv2>(10_000, 0.1, 0.999, 1.0);
+    // let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::q_learning::q_learning(&mut envs::secret::SecretEnv2::new(), 10_000, 0.1, 0.999, 1.0, &mut rng);
+    // println!("time : {:?}", duration);
     // for (a, q) in q_values[0].iter().enumerate() {
     //     println!("Q(s=0, a={}) = {}", a, q);
     // }
     //
     // println!("Q-Learning on SecretEnv3...");
-    // let q_values = algorithms::q_learning::q_learning::<envs::secret::SecretEnv3>(10_000, 0.1, 0.999, 1.0);
+    // let algorithms::training_result::LearningResult { q_values, duration, .. } = algorithms::q_learning::q_learning(&mut envs::secret::SecretEnv3::new(), 10_000, 0.1, 0.999, 1.0, &mut rng);
+    // println!("time : {:?}", duration);
     // for (a, q) in q_values[0].iter().enumerate() {
     //     println!("Q(s=0, a={}) = {}", a, q);
     // }
@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+use std::fmt;
+use rand::prelude::SliceRandom;
+use rand::Rng;
+use crate::contracts::environment::{Environment, Transition};
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+const REWARD_MATCH_TOLERANCE: f32 = 1e-4;
+
+// Issue observée d'une transition : état suivant et indice de la récompense déclarée,
+// ou bits de la récompense brute si elle ne correspond à aucune récompense du modèle
+type Outcome = (usize, Result<usize, u32>);
+
+// Écart observé pour une paire (s, a) entre les déroulés du ModelFreeEnv et le modèle déclaré par MDPEnv
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairMismatch {
+    pub state: usize,
+    pub action: usize,
+    pub samples: usize,
+    pub declared_total: f32,
+    // Statistique du khi-deux, degrés de liberté et p-valeur, si la paire a assez d'échantillons pour être testée
+    pub chi_square: Option<(f64, usize, f64)>,
+    // (s', récompense observée, nombre d'occurrences) pour des issues que le modèle déclare impossibles
+    pub impossible_outcomes: Vec<(usize, f32, usize)>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConsistencyReport {
+    pub num_transitions: usize,
+    pub tested_pairs: usize,
+    pub mismatches: Vec<PairMismatch>,
+    // États où `is_game_over` est vrai alors que le modèle déclare des transitions sortantes
+    pub terminal_but_declared_transitions: Vec<usize>,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty() && self.terminal_but_declared_transitions.is_empty()
+    }
+}
+
+// Déroule `num_rollouts` épisodes d'au plus `max_steps` pas, chacun depuis `reset_to_random_state` et avec des actions
+// tirées uniformément parmi `available_actions`, puis compare les fréquences observées de (s', r) pour chaque (s, a)
+// aux probabilités déclarées par le modèle de `env` avec un test du khi-deux au seuil `significance`.
+// Les paires ayant moins de `min_samples` échantillons ne sont pas testées (seules les issues impossibles sont signalées).
+pub fn check_consistency<TEnv: Mdp + Environment>(
+    env: &mut TEnv,
+    num_rollouts: usize,
+    max_steps: usize,
+    min_samples: usize,
+    significance: f64,
+    rng: &mut impl Rng,
+) -> ConsistencyReport {
+    let model = SparseTransitionModel::from_mdp(env);
+
+    // (s, a) -> issue -> occurrences
+    let mut counts: HashMap<(usize, usize), HashMap<Outcome, usize>> = HashMap::new();
+    let mut observed_terminal_states = vec![false; model.num_states()];
+    let mut num_transitions = 0;
+
+    for _ in 0..num_rollouts {
+        env.reset_to_random_state(rng);
+        for _ in 0..max_steps {
+            let s = env.state_id();
+            if env.is_game_over() {
+                observed_terminal_states[s] = true;
+                break;
+            }
+            let a = *env.available_actions().choose(rng).unwrap();
+            let Transition { reward: r, next_state: s_p, .. } = env.step(a);
+            let reward = model.rewards()
+                .iter()
+                .position(|declared| (declared - r).abs() < REWARD_MATCH_TOLERANCE)
+                .ok_or(r.to_bits());
+            *counts.entry((s, a)).or_default().entry((s_p, reward)).or_default() += 1;
+            num_transitions += 1;
+        }
+    }
+
+    let mut report = ConsistencyReport {
+        num_transitions,
+        ..ConsistencyReport::default()
+    };
+
+    report.terminal_but_declared_transitions = (0..model.num_states())
+        .filter(|&s| observed_terminal_states[s] && (0..model.num_actions()).any(|a| !model.transitions(s, a).is_empty()))
+        .collect();
+
+    let mut pairs = counts.keys().cloned().collect::<Vec<(usize, usize)>>();
+    pairs.sort();
+    for (s, a) in pairs {
+        let outcomes = &counts[&(s, a)];
+        let samples = outcomes.values().sum::<usize>();
+        let declared = model.transitions(s, a);
+        let declared_total = declared.iter().fold(0.0, |total, &(_, _, probability)| total + probability);
+
+        let mut impossible_outcomes = outcomes.iter()
+            .filter(|((s_p, reward), _)| match reward {
+                Ok(r_index) => !declared.iter().any(|&(d_s_p, d_r_index, probability)| d_s_p == *s_p && d_r_index == *r_index && probability > 0.0),
+                Err(_) => true,
+            })
+            .map(|(&(s_p, reward), &count)| {
+                let r = match reward {
+                    Ok(r_index) => model.rewards()[r_index],
+                    Err(bits) => f32::from_bits(bits),
+                };
+                (s_p, r, count)
+            })
+            .collect::<Vec<(usize, f32, usize)>>();
+        impossible_outcomes.sort_by(|o1, o2| o1.0.cmp(&o2.0).then(o1.1.total_cmp(&o2.1)));
+
+        let chi_square = if samples >= min_samples && !declared.is_empty() {
+            // Les probabilités déclarées sont prises telles quelles : un modèle qui ne somme pas à 1 est rejeté
+            let statistic = declared.iter()
+                .filter(|&&(_, _, probability)| probability > 0.0)
+                .map(|&(s_p, r_index, probability)| {
+                    let expected = samples as f64 * probability as f64;
+                    let observed = *outcomes.get(&(s_p, Ok(r_index))).unwrap_or(&0) as f64;
+                    (observed - expected).powi(2) / expected
+                })
+                .sum::<f64>();
+            let degrees_of_freedom = declared.len().saturating_sub(1).max(1);
+            Some((statistic, degrees_of_freedom, chi_square_survival(statistic, degrees_of_freedom)))
+        } else {
+            None
+        };
+        if chi_square.is_some() {
+            report.tested_pairs += 1;
+        }
+
+        let rejected = chi_square.is_some_and(|(_, _, p_value)| p_value < significance);
+        if rejected || !impossible_outcomes.is_empty() {
+            report.mismatches.push(PairMismatch {
+                state: s,
+                action: a,
+                samples,
+                declared_total,
+                chi_square,
+                impossible_outcomes,
+            });
+        }
+    }
+
+    report
+}
+
+// P(X >= x) pour X suivant une loi du khi-deux à k degrés de liberté, soit Q(k / 2, x / 2)
+fn chi_square_survival(x: f64, degrees_of_freedom: usize) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let a = degrees_of_freedom as f64 / 2.0;
+    let x = x / 2.0;
+    if x < a + 1.0 {
+        // Développement en série de la fonction gamma incomplète inférieure
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..1000 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-12 {
+                break;
+            }
+        }
+        1.0 - (sum.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+    } else {
+        // Fraction continue (méthode de Lentz) pour la fonction gamma incomplète supérieure
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-12 {
+                break;
+            }
+        }
+        (h.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+    }
+}
+
+// Approximation de Lanczos de ln(Γ(x)) pour x > 0
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+impl fmt::Display for ConsistencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} sampled transitions, {} (s, a) pairs tested", self.num_transitions, self.tested_pairs)?;
+        writeln!(f, "MDPEnv and ModelFreeEnv are {}", if self.is_consistent() { "consistent" } else { "INCONSISTENT" })?;
+        if !self.terminal_but_declared_transitions.is_empty() {
+            writeln!(f, "States where is_game_over() is true but transitions are declared: {:?}", self.terminal_but_declared_transitions)?;
+        }
+        for mismatch in self.mismatches.iter() {
+            write!(f, "s={}, a={} : {} samples, declared probabilities sum to {}", mismatch.state, mismatch.action, mismatch.samples, mismatch.declared_total)?;
+            if let Some((statistic, degrees_of_freedom, p_value)) = mismatch.chi_square {
+                write!(f, ", chi2 = {:.2} ({} dof), p = {:.3e}", statistic, degrees_of_freedom, p_value)?;
+            }
+            writeln!(f)?;
+            for (s_p, r, count) in mismatch.impossible_outcomes.iter() {
+                writeln!(f, "  observed {} times s'={}, r={} which the model declares impossible", count, s_p, r)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::grid_world::GridWorld;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn ln_gamma_matches_known_values() {
+        assert!((ln_gamma(5.0) - 24f64.ln()).abs() < 1e-9);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-9);
+        assert!(ln_gamma(1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn chi_square_survival_matches_table_quantiles() {
+        // (x, degrés de liberté, P(X >= x)) des tables usuelles, sur les deux branches (série et fraction continue)
+        for (x, degrees_of_freedom, p_value) in [
+            (3.841, 1, 0.05),
+            (6.635, 1, 0.01),
+            (5.991, 2, 0.05),
+            (1.064, 4, 0.90),
+            (18.307, 10, 0.05),
+            (3.940, 10, 0.95),
+        ] {
+            let survival = chi_square_survival(x, degrees_of_freedom);
+            assert!((survival - p_value).abs() < 1e-3, "x={}, k={} : {} instead of {}", x, degrees_of_freedom, survival, p_value);
+        }
+        assert_eq!(chi_square_survival(0.0, 3), 1.0);
+    }
+
+    #[test]
+    fn line_world_is_consistent() {
+        let report = check_consistency(&mut LineWorld::<5>::new(), 2_000, 100, 30, 0.001, &mut StdRng::seed_from_u64(0));
+        assert!(report.is_consistent(), "{}", report);
+        assert_eq!(report.tested_pairs, 6);
+    }
+
+    #[test]
+    fn grid_world_terminal_states_disagree() {
+        let report = check_consistency(&mut GridWorld::<4, 4>::new(), 2_000, 100, 30, 0.001, &mut StdRng::seed_from_u64(0));
+        assert!(!report.is_consistent());
+        // is_game_over s'arrête en (0, COLUMNS - 1) et (ROWS - 1, 0), que le modèle ne traite pas comme terminaux...
+        assert_eq!(report.terminal_but_declared_transitions, vec![3, 12]);
+        // ...alors que le modèle n'a aucune transition depuis l'état 0, où le ModelFreeEnv continue de jouer
+        assert!(report.mismatches.iter().any(|mismatch| mismatch.state == 0 && mismatch.declared_total == 0.0 && !mismatch.impossible_outcomes.is_empty()));
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use rand::prelude::SliceRandom;
+use rand::{Rng, RngCore};
+use crate::contracts::environment::{Environment, Transition};
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+const REWARD_MATCH_TOLERANCE: f32 = 1e-6;
+// Épisodes vides consécutifs (départ terminal ou max_episode_steps = 0) au-delà desquels on abandonne
+const MAX_CONSECUTIVE_EMPTY_EPISODES: usize = 1_000;
+
+pub fn uniform_random_policy(_state: usize, available_actions: &[usize], rng: &mut dyn RngCore) -> usize {
+    *available_actions.choose(rng).unwrap()
+}
+
+// Modèle "certainty-equivalence" estimé à partir de déroulés de `env` : p(s', r | s, a) est la fréquence
+// observée de (s', r) après (s, a). On consomme exactement `num_steps` appels à `step`, en épisodes d'au plus
+// `max_episode_steps` pas, démarrant depuis `reset_to_random_state` si `exploring_starts`, sinon depuis `reset`.
+// Les récompenses du modèle sont les différences de score distinctes observées.
+// Les paires (s, a) jamais essayées et les états terminaux n'ont aucune transition, comme dans les MDPEnv du dépôt.
+pub fn estimate_mdp(
+    env: &mut impl Environment,
+    num_steps: usize,
+    max_episode_steps: usize,
+    exploring_starts: bool,
+    mut policy: impl FnMut(usize, &[usize], &mut dyn RngCore) -> usize,
+    rng: &mut impl Rng,
+) -> SparseTransitionModel {
+    let num_states = env.num_states();
+    let num_actions = env.num_actions();
+
+    let mut rewards: Vec<f32> = vec![];
+    // (s, a) -> (s', indice de récompense) -> occurrences
+    let mut counts: HashMap<(usize, usize), HashMap<(usize, usize), usize>> = HashMap::new();
+
+    let mut steps = 0;
+    let mut consecutive_empty_episodes = 0;
+    while steps < num_steps {
+        if exploring_starts {
+            env.reset_to_random_state(rng);
+        } else {
+            env.reset();
+        }
+        let mut episode_steps = 0;
+        while !env.is_game_over() && episode_steps < max_episode_steps && steps < num_steps {
+            let s = env.state_id();
+            let a = policy(s, &env.available_actions(), rng);
+            let Transition { reward: r, next_state: s_p, .. } = env.step(a);
+            let r_index = match rewards.iter().position(|reward| (reward - r).abs() < REWARD_MATCH_TOLERANCE) {
+                Some(r_index) => r_index,
+                None => {
+                    rewards.push(r);
+                    rewards.len() - 1
+                }
+            };
+            *counts.entry((s, a)).or_default().entry((s_p, r_index)).or_default() += 1;
+            steps += 1;
+            episode_steps += 1;
+        }
+        // Un épisode qui ne peut pas avancer ne doit pas faire boucler indéfiniment : `reset` revient toujours
+        // au même état, un seul épisode vide suffit ; avec des départs aléatoires on tolère une série de départs terminaux
+        if episode_steps == 0 {
+            consecutive_empty_episodes += 1;
+            if !exploring_starts || consecutive_empty_episodes >= MAX_CONSECUTIVE_EMPTY_EPISODES {
+                break;
+            }
+        } else {
+            consecutive_empty_episodes = 0;
+        }
+    }
+
+    let mut row_offsets = Vec::with_capacity(num_states * num_actions + 1);
+    let mut transitions = vec![];
+    row_offsets.push(0);
+    for s in 0..num_states {
+        for a in 0..num_actions {
+            if let Some(outcomes) = counts.get(&(s, a)) {
+                let total = outcomes.values().sum::<usize>() as f32;
+                let mut outcomes = outcomes.iter().collect::<Vec<_>>();
+                outcomes.sort();
+                for (&(s_p, r_index), &count) in outcomes {
+                    transitions.push((s_p, r_index, count as f32 / total));
+                }
+            }
+            row_offsets.push(transitions.len());
+        }
+    }
+
+    SparseTransitionModel::from_parts(num_states, num_actions, rewards, row_offsets, transitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::contracts::model_free_env::ModelFreeEnv;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn stops_when_no_episode_can_progress() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for exploring_starts in [false, true] {
+            let model = estimate_mdp(&mut LineWorld::<5>::new(), 100, 0, exploring_starts, uniform_random_policy, &mut rng);
+            assert_eq!(model.num_transitions(), 0);
+        }
+    }
+
+    #[test]
+    fn consumes_exactly_num_steps() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Chaque appel à la politique est suivi d'exactement un appel à `step`
+        let mut steps = 0;
+        let counting_policy = |s: usize, available_actions: &[usize], rng: &mut dyn RngCore| {
+            steps += 1;
+            uniform_random_policy(s, available_actions, rng)
+        };
+        let model = estimate_mdp(&mut LineWorld::<5>::new(), 500, 100, true, counting_policy, &mut rng);
+        assert_eq!(steps, 500);
+        assert!(model.num_transitions() > 0);
+        assert_eq!(model.rewards().len(), 3);
+    }
+}
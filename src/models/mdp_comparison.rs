@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MdpComparisonReport {
+    // (états, actions, récompenses) de chacun des deux modèles
+    pub dimensions: [(usize, usize, usize); 2],
+    // (indice, r1, r2) pour les récompenses communes qui diffèrent
+    pub reward_differences: Vec<(usize, f32, f32)>,
+    // (s, a, plus grand écart |p1 - p2| sur les (s', r)) pour les paires qui diffèrent,
+    // seulement si les deux modèles ont les mêmes états et actions
+    pub transition_differences: Vec<(usize, usize, f32)>,
+}
+
+impl MdpComparisonReport {
+    pub fn is_identical(&self) -> bool {
+        self.dimensions[0] == self.dimensions[1] && self.reward_differences.is_empty() && self.transition_differences.is_empty()
+    }
+}
+
+// Compare deux révisions d'un même MDP, par exemple un environnement secret de deux versions de la bibliothèque.
+// Les écarts de probabilité ou de récompense d'au plus `tolerance` sont ignorés.
+pub fn compare_mdps(mdp1: &impl Mdp, mdp2: &impl Mdp, tolerance: f32) -> MdpComparisonReport {
+    compare_models(&SparseTransitionModel::from_mdp(mdp1), &SparseTransitionModel::from_mdp(mdp2), tolerance)
+}
+
+pub fn compare_models(model1: &SparseTransitionModel, model2: &SparseTransitionModel, tolerance: f32) -> MdpComparisonReport {
+    let mut report = MdpComparisonReport {
+        dimensions: [
+            (model1.num_states(), model1.num_actions(), model1.num_rewards()),
+            (model2.num_states(), model2.num_actions(), model2.num_rewards()),
+        ],
+        ..MdpComparisonReport::default()
+    };
+
+    report.reward_differences = model1.rewards().iter()
+        .zip(model2.rewards().iter())
+        .enumerate()
+        .filter(|(_, (r1, r2))| (*r1 - *r2).abs() > tolerance)
+        .map(|(r_index, (&r1, &r2))| (r_index, r1, r2))
+        .collect();
+
+    if model1.num_states() != model2.num_states() || model1.num_actions() != model2.num_actions() {
+        return report;
+    }
+
+    for s in 0..model1.num_states() {
+        for a in 0..model1.num_actions() {
+            // (s', indice de récompense) -> (p1, p2)
+            let mut probabilities: HashMap<(usize, usize), (f32, f32)> = HashMap::new();
+            for &(s_p, r_index, probability) in model1.transitions(s, a) {
+                probabilities.entry((s_p, r_index)).or_default().0 += probability;
+            }
+            for &(s_p, r_index, probability) in model2.transitions(s, a) {
+                probabilities.entry((s_p, r_index)).or_default().1 += probability;
+            }
+            let max_difference = probabilities.values().map(|(p1, p2)| (p1 - p2).abs()).fold(0.0, f32::max);
+            if max_difference > tolerance {
+                report.transition_differences.push((s, a, max_difference));
+            }
+        }
+    }
+
+    report
+}
+
+impl fmt::Display for MdpComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Models are {}", if self.is_identical() { "identical" } else { "DIFFERENT" })?;
+        for (i, (num_states, num_actions, num_rewards)) in self.dimensions.iter().enumerate() {
+            writeln!(f, "Model {} : {} states, {} actions, {} rewards", i + 1, num_states, num_actions, num_rewards)?;
+        }
+        writeln!(f, "{} different rewards:", self.reward_differences.len())?;
+        for (r_index, r1, r2) in self.reward_differences.iter() {
+            writeln!(f, "  r={} : {} vs {}", r_index, r1, r2)?;
+        }
+        write!(f, "{} (s, a) pairs with different transitions", self.transition_differences.len())?;
+        for (s, a, max_difference) in self.transition_differences.iter() {
+            write!(f, "\n  s={}, a={} : max |p1 - p2| = {}", s, a, max_difference)?;
+        }
+        Ok(())
+    }
+}
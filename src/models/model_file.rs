@@ -0,0 +1,158 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Write};
+use std::path::Path;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+// Format binaire (little-endian) d'un MDP extrait :
+//   en-tête   : MAGIC, version (u32), nombre d'états, d'actions, de récompenses et de transitions (u64)
+//   récompenses : num_rewards x f32
+//   lignes    : (num_states * num_actions + 1) x u64, les row_offsets du modèle CSR
+//   transitions : num_transitions x (état suivant u64, indice de récompense u64, probabilité f32)
+const MAGIC: &[u8; 4] = b"MDPM";
+const VERSION: u32 = 1;
+
+pub fn save_model(model: &SparseTransitionModel, path: impl AsRef<Path>) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    for count in [model.num_states(), model.num_actions(), model.num_rewards(), model.num_transitions()] {
+        writer.write_all(&(count as u64).to_le_bytes())?;
+    }
+    for reward in model.rewards() {
+        writer.write_all(&reward.to_le_bytes())?;
+    }
+    for offset in model.row_offsets() {
+        writer.write_all(&(*offset as u64).to_le_bytes())?;
+    }
+    for &(s_p, r_index, probability) in model.all_transitions() {
+        writer.write_all(&(s_p as u64).to_le_bytes())?;
+        writer.write_all(&(r_index as u64).to_le_bytes())?;
+        writer.write_all(&probability.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+pub fn load_model(path: impl AsRef<Path>) -> std::io::Result<SparseTransitionModel> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(Error::new(ErrorKind::InvalidData, "Not an MDP model file"));
+    }
+    let version = read_u32(&mut reader)?;
+    if version != VERSION {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Unsupported MDP model file version {}", version)));
+    }
+
+    let num_states = read_usize(&mut reader)?;
+    let num_actions = read_usize(&mut reader)?;
+    let num_rewards = read_usize(&mut reader)?;
+    let num_transitions = read_usize(&mut reader)?;
+
+    let rewards = (0..num_rewards).map(|_| read_f32(&mut reader)).collect::<std::io::Result<Vec<f32>>>()?;
+    let num_rows = num_states.checked_mul(num_actions)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Too many state-action pairs"))?;
+    let row_offsets = (0..=num_rows).map(|_| read_usize(&mut reader)).collect::<std::io::Result<Vec<usize>>>()?;
+    let transitions = (0..num_transitions)
+        .map(|_| Ok((read_usize(&mut reader)?, read_usize(&mut reader)?, read_f32(&mut reader)?)))
+        .collect::<std::io::Result<Vec<(usize, usize, f32)>>>()?;
+
+    if row_offsets[0] != 0 || row_offsets.windows(2).any(|w| w[0] > w[1]) || row_offsets[num_rows] != num_transitions {
+        return Err(Error::new(ErrorKind::InvalidData, "Inconsistent row offsets"));
+    }
+    if transitions.iter().any(|&(s_p, r_index, _)| s_p >= num_states || r_index >= num_rewards) {
+        return Err(Error::new(ErrorKind::InvalidData, "Transition refers to an unknown state or reward"));
+    }
+
+    Ok(SparseTransitionModel::from_parts(num_states, num_actions, rewards, row_offsets, transitions))
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_usize(reader: &mut impl Read) -> std::io::Result<usize> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    usize::try_from(u64::from_le_bytes(bytes)).map_err(|_| Error::new(ErrorKind::InvalidData, "Count does not fit in usize"))
+}
+
+fn read_f32(reader: &mut impl Read) -> std::io::Result<f32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(f32::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::grid_world::GridWorld;
+    use crate::envs::line_world::LineWorld;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("model_file_{}_{}.mdp", name, std::process::id()))
+    }
+
+    fn saved_bytes(model: &SparseTransitionModel, name: &str) -> Vec<u8> {
+        let path = temp_path(name);
+        save_model(model, &path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    fn load_bytes(bytes: &[u8], name: &str) -> std::io::Result<SparseTransitionModel> {
+        let path = temp_path(name);
+        std::fs::write(&path, bytes).unwrap();
+        let model = load_model(&path);
+        std::fs::remove_file(&path).unwrap();
+        model
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        for (name, model) in [
+            ("line_world", SparseTransitionModel::from_mdp_env::<LineWorld<5>>()),
+            ("grid_world", SparseTransitionModel::from_mdp_env::<GridWorld<3, 5>>()),
+        ] {
+            let loaded = load_bytes(&saved_bytes(&model, name), name).unwrap();
+            assert_eq!(
+                (loaded.num_states(), loaded.num_actions(), loaded.num_rewards(), loaded.num_transitions()),
+                (model.num_states(), model.num_actions(), model.num_rewards(), model.num_transitions()),
+            );
+            assert_eq!(loaded.rewards(), model.rewards());
+            assert_eq!(loaded.row_offsets(), model.row_offsets());
+            assert_eq!(loaded.all_transitions(), model.all_transitions());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let model = SparseTransitionModel::from_mdp_env::<LineWorld<5>>();
+        let bytes = saved_bytes(&model, "malformed");
+        let expect_error = |bytes: &[u8], kind: ErrorKind| {
+            let error = load_bytes(bytes, "malformed").err().expect("malformed file was accepted");
+            assert_eq!(error.kind(), kind, "{}", error);
+        };
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        expect_error(&bad_magic, ErrorKind::InvalidData);
+
+        let mut bad_version = bytes.clone();
+        bad_version[4..8].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        expect_error(&bad_version, ErrorKind::InvalidData);
+
+        expect_error(&bytes[..bytes.len() - 3], ErrorKind::UnexpectedEof);
+        expect_error(&bytes[..6], ErrorKind::UnexpectedEof);
+
+        // Le premier row_offset suit l'en-tête (4 + 4 + 4 x 8 octets) et les récompenses
+        let first_offset = 40 + 4 * model.num_rewards();
+        let mut bad_offsets = bytes.clone();
+        bad_offsets[first_offset..first_offset + 8].copy_from_slice(&1u64.to_le_bytes());
+        expect_error(&bad_offsets, ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,34 @@
+use rand::prelude::SliceRandom;
+use rand::Rng;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+// MDP aléatoire : chaque (s, a) mène à `branching` couples (s', r) tirés au hasard, avec des probabilités
+// normalisées pour sommer à 1. Les récompenses sont tirées uniformément dans [-1, 1].
+pub fn random_mdp(
+    num_states: usize,
+    num_actions: usize,
+    num_rewards: usize,
+    branching: usize,
+    rng: &mut impl Rng,
+) -> SparseTransitionModel {
+    let rewards = (0..num_rewards).map(|_| rng.gen_range(-1.0..=1.0)).collect::<Vec<f32>>();
+    let outcomes = (0..num_states)
+        .flat_map(|s_p| (0..num_rewards).map(move |r_index| (s_p, r_index)))
+        .collect::<Vec<(usize, usize)>>();
+
+    let mut row_offsets = Vec::with_capacity(num_states * num_actions + 1);
+    let mut transitions = vec![];
+    row_offsets.push(0);
+    for _ in 0..(num_states * num_actions) {
+        let mut chosen = outcomes.choose_multiple(rng, branching.clamp(1, outcomes.len())).cloned().collect::<Vec<(usize, usize)>>();
+        chosen.sort();
+        let weights = chosen.iter().map(|_| rng.gen_range(0.01..1.0)).collect::<Vec<f32>>();
+        let total = weights.iter().sum::<f32>();
+        for ((s_p, r_index), weight) in chosen.into_iter().zip(weights) {
+            transitions.push((s_p, r_index, weight / total));
+        }
+        row_offsets.push(transitions.len());
+    }
+
+    SparseTransitionModel::from_parts(num_states, num_actions, rewards, row_offsets, transitions)
+}
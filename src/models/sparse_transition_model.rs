@@ -0,0 +1,260 @@
+use crate::contracts::mdp::{Mdp, StaticMdp};
+use crate::contracts::mdp_env::MDPEnv;
+
+type RowChunk = (Vec<usize>, Vec<(usize, usize, f32)>);
+
+// Modèle de transition creux au format CSR : pour chaque paire (s, a), la liste des
+// (état suivant, indice de récompense, probabilité) de probabilité non nulle.
+// Les transitions de (s, a) sont transitions[row_offsets[s * num_actions + a]..row_offsets[s * num_actions + a + 1]].
+pub struct SparseTransitionModel {
+    num_states: usize,
+    num_actions: usize,
+    rewards: Vec<f32>,
+    row_offsets: Vec<usize>,
+    transitions: Vec<(usize, usize, f32)>,
+}
+
+impl SparseTransitionModel {
+    // Une ligne par paire (s, a), obtenue par `nonzero_transitions`. Pour un environnement qui ne la redéfinit pas
+    // (SecretEnv, dont la bibliothèque n'expose que `transition_probability`), chaque ligne reste un balayage
+    // de tous les (s', r) : le temps de construction croît alors avec |S|² |A| |R|, et seul `from_mdp_env_parallel` le réduit.
+    pub fn from_mdp_env<TEnv: MDPEnv>() -> Self {
+        SparseTransitionModel::from_mdp(&StaticMdp::<TEnv>::new())
+    }
+
+    // Même modèle que `from_mdp_env`, les paires (s, a) étant réparties par blocs contigus entre `num_threads` threads.
+    // Chaque thread passe par ses propres thread_local (pour SecretEnv, son propre handle sur la bibliothèque partagée).
+    pub fn from_mdp_env_parallel<TEnv: MDPEnv>(num_threads: usize) -> Self {
+        SparseTransitionModel::from_mdp_parallel(&StaticMdp::<TEnv>::new(), num_threads)
+    }
+
+    pub fn from_mdp(mdp: &impl Mdp) -> Self {
+        let num_rows = mdp.num_states() * mdp.num_actions();
+        let chunk = extract_rows(mdp, 0, num_rows);
+        SparseTransitionModel::from_chunks(mdp, vec![chunk])
+    }
+
+    pub fn from_mdp_parallel<TMdp: Mdp + Sync>(mdp: &TMdp, num_threads: usize) -> Self {
+        let num_rows = mdp.num_states() * mdp.num_actions();
+        let num_threads = num_threads.clamp(1, num_rows.max(1));
+        let rows_per_thread = num_rows.div_ceil(num_threads);
+
+        let chunks = if num_threads == 1 {
+            vec![extract_rows(mdp, 0, num_rows)]
+        } else {
+            std::thread::scope(|scope| {
+                let workers = (0..num_threads)
+                    .map(|i| {
+                        let first_row = (i * rows_per_thread).min(num_rows);
+                        let last_row = ((i + 1) * rows_per_thread).min(num_rows);
+                        scope.spawn(move || extract_rows(mdp, first_row, last_row))
+                    })
+                    .collect::<Vec<_>>();
+                workers.into_iter().map(|worker| worker.join().expect("Transition model worker panicked")).collect::<Vec<_>>()
+            })
+        };
+
+        SparseTransitionModel::from_chunks(mdp, chunks)
+    }
+
+    // Assemble les blocs de lignes (longueurs des lignes, transitions) produits par `extract_rows`, dans l'ordre
+    fn from_chunks(mdp: &impl Mdp, chunks: Vec<RowChunk>) -> Self {
+        let rewards = (0..mdp.num_rewards()).map(|r_index| mdp.reward(r_index)).collect::<Vec<f32>>();
+        let mut row_offsets = Vec::with_capacity(mdp.num_states() * mdp.num_actions() + 1);
+        let mut transitions = Vec::with_capacity(chunks.iter().map(|(_, chunk)| chunk.len()).sum());
+        row_offsets.push(0);
+        for (row_lengths, chunk) in chunks {
+            for row_length in row_lengths {
+                row_offsets.push(row_offsets.last().unwrap() + row_length);
+            }
+            transitions.extend(chunk);
+        }
+
+        SparseTransitionModel::from_parts(mdp.num_states(), mdp.num_actions(), rewards, row_offsets, transitions)
+    }
+
+    pub fn from_parts(
+        num_states: usize,
+        num_actions: usize,
+        rewards: Vec<f32>,
+        row_offsets: Vec<usize>,
+        transitions: Vec<(usize, usize, f32)>,
+    ) -> Self {
+        assert_eq!(row_offsets.len(), num_states * num_actions + 1, "row_offsets must have one entry per (s, a) plus one");
+        assert_eq!(*row_offsets.last().unwrap(), transitions.len(), "row_offsets must end at the number of transitions");
+        SparseTransitionModel {
+            num_states,
+            num_actions,
+            rewards,
+            row_offsets,
+            transitions,
+        }
+    }
+
+    pub fn num_states(&self) -> usize {
+        self.num_states
+    }
+
+    pub fn num_actions(&self) -> usize {
+        self.num_actions
+    }
+
+    pub fn num_rewards(&self) -> usize {
+        self.rewards.len()
+    }
+
+    pub fn rewards(&self) -> &[f32] {
+        &self.rewards
+    }
+
+    pub fn num_transitions(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    pub fn all_transitions(&self) -> &[(usize, usize, f32)] {
+        &self.transitions
+    }
+
+    pub fn transitions(&self, state: usize, action: usize) -> &[(usize, usize, f32)] {
+        let row = state * self.num_actions + action;
+        &self.transitions[self.row_offsets[row]..self.row_offsets[row + 1]]
+    }
+
+    // sum_{s', r} p(s', r | s, a) * (r + gamma * V(s'))
+    pub fn q_value(&self, state: usize, action: usize, value_function: &[f32], gamma: f32) -> f32 {
+        self.transitions(state, action)
+            .iter()
+            .fold(0.0, |total, &(s_p, r_index, probability)| total + probability * (self.rewards[r_index] + gamma * value_function[s_p]))
+    }
+
+    // Version continue du modèle, comme `ContinuingTask` : les transitions vers un état terminal
+    // (sans aucune transition sortante) arrivent à la place sur `start_state`, avec la même récompense.
+    pub fn to_continuing(&self, start_state: usize) -> SparseTransitionModel {
+        let terminal = (0..self.num_states)
+            .map(|s| (0..self.num_actions).all(|a| self.transitions(s, a).is_empty()))
+            .collect::<Vec<bool>>();
+
+        let mut row_offsets = Vec::with_capacity(self.row_offsets.len());
+        let mut transitions = Vec::with_capacity(self.transitions.len());
+        row_offsets.push(0);
+        for s in 0..self.num_states {
+            for a in 0..self.num_actions {
+                let row_start = transitions.len();
+                for &(s_p, r_index, probability) in self.transitions(s, a) {
+                    let s_p = if terminal[s_p] { start_state } else { s_p };
+                    match transitions[row_start..].iter_mut().find(|(other_s_p, other_r_index, _)| *other_s_p == s_p && *other_r_index == r_index) {
+                        Some((_, _, other_probability)) => *other_probability += probability,
+                        None => transitions.push((s_p, r_index, probability)),
+                    }
+                }
+                row_offsets.push(transitions.len());
+            }
+        }
+
+        SparseTransitionModel::from_parts(self.num_states, self.num_actions, self.rewards.clone(), row_offsets, transitions)
+    }
+}
+
+fn extract_rows(mdp: &impl Mdp, first_row: usize, last_row: usize) -> RowChunk {
+    let num_actions = mdp.num_actions();
+    let mut row_lengths = Vec::with_capacity(last_row - first_row);
+    let mut transitions = vec![];
+    for row in first_row..last_row {
+        let (s, a) = (row / num_actions, row % num_actions);
+        let row_transitions = mdp.nonzero_transitions(s, a);
+        row_lengths.push(row_transitions.len());
+        transitions.extend(row_transitions);
+    }
+    (row_lengths, transitions)
+}
+
+impl Mdp for SparseTransitionModel {
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+
+    fn num_actions(&self) -> usize {
+        self.num_actions
+    }
+
+    fn num_rewards(&self) -> usize {
+        self.rewards.len()
+    }
+
+    fn reward(&self, index: usize) -> f32 {
+        self.rewards[index]
+    }
+
+    fn transition_probability(&self, state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+        self.transitions(state, action)
+            .iter()
+            .filter(|&&(s_p, r_index, _)| s_p == next_state && r_index == reward_index)
+            .fold(0.0, |total, &(_, _, probability)| total + probability)
+    }
+
+    fn nonzero_transitions(&self, state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        self.transitions(state, action).to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::grid_world::GridWorld;
+    use crate::envs::line_world::LineWorld;
+    use crate::envs::map_grid_world::MapGridWorld;
+
+    // Masque `nonzero_transitions` pour retomber sur le balayage complet par défaut
+    struct FullScan<'a, TMdp: Mdp>(&'a TMdp);
+
+    impl<TMdp: Mdp> Mdp for FullScan<'_, TMdp> {
+        fn num_states(&self) -> usize {
+            self.0.num_states()
+        }
+
+        fn num_actions(&self) -> usize {
+            self.0.num_actions()
+        }
+
+        fn num_rewards(&self) -> usize {
+            self.0.num_rewards()
+        }
+
+        fn reward(&self, index: usize) -> f32 {
+            self.0.reward(index)
+        }
+
+        fn transition_probability(&self, state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+            self.0.transition_probability(state, action, next_state, reward_index)
+        }
+    }
+
+    fn assert_same_model(mdp: &impl Mdp) {
+        let enumerated = SparseTransitionModel::from_mdp(mdp);
+        let scanned = SparseTransitionModel::from_mdp(&FullScan(mdp));
+        assert_eq!(enumerated.row_offsets(), scanned.row_offsets());
+        assert_eq!(enumerated.all_transitions(), scanned.all_transitions());
+    }
+
+    #[test]
+    fn nonzero_transitions_match_full_scan() {
+        assert_same_model(&StaticMdp::<LineWorld<5>>::new());
+        assert_same_model(&StaticMdp::<LineWorld<3>>::new());
+        assert_same_model(&StaticMdp::<GridWorld<4, 4>>::new());
+        assert_same_model(&StaticMdp::<GridWorld<3, 5>>::new());
+        assert_same_model(&MapGridWorld::from_map("S.#\n.XG").unwrap());
+    }
+
+    #[test]
+    fn parallel_build_matches_sequential_build() {
+        let mdp = StaticMdp::<GridWorld<4, 4>>::new();
+        let sequential = SparseTransitionModel::from_mdp(&mdp);
+        let parallel = SparseTransitionModel::from_mdp_parallel(&mdp, 3);
+        assert_eq!(sequential.row_offsets(), parallel.row_offsets());
+        assert_eq!(sequential.all_transitions(), parallel.all_transitions());
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+use crate::contracts::mdp_env::MDPEnv;
+use crate::models::model_file::load_model;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+// MDPEnv n'a que des fonctions associées : les modèles sont donc rangés dans un registre global,
+// indexé par MODEL_ID, partagé entre threads (contrairement à SECRET_LIB).
+fn registry() -> &'static RwLock<HashMap<u8, Arc<SparseTransitionModel>>> {
+    static MODELS: OnceLock<RwLock<HashMap<u8, Arc<SparseTransitionModel>>>> = OnceLock::new();
+    MODELS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+// MDP tabulaire adossé à un SparseTransitionModel, par exemple rechargé depuis le disque avec `load_model`.
+// Il faut installer le modèle avant d'utiliser le type :
+//   TabularMdp::<0>::load("secret_env_0.mdp")?;
+//   value_iteration::<TabularMdp<0>>(0.999, 0.001);
+pub struct TabularMdp<const MODEL_ID: u8>;
+
+impl<const MODEL_ID: u8> TabularMdp<MODEL_ID> {
+    pub fn install(model: SparseTransitionModel) {
+        registry().write().unwrap().insert(MODEL_ID, Arc::new(model));
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<()> {
+        TabularMdp::<MODEL_ID>::install(load_model(path)?);
+        Ok(())
+    }
+
+    pub fn model() -> Arc<SparseTransitionModel> {
+        registry().read().unwrap().get(&MODEL_ID).cloned()
+            .unwrap_or_else(|| panic!("No model installed for TabularMdp<{}>", MODEL_ID))
+    }
+}
+
+impl<const MODEL_ID: u8> MDPEnv for TabularMdp<MODEL_ID> {
+    fn num_states() -> usize {
+        TabularMdp::<MODEL_ID>::model().num_states()
+    }
+
+    fn num_actions() -> usize {
+        TabularMdp::<MODEL_ID>::model().num_actions()
+    }
+
+    fn num_rewards() -> usize {
+        TabularMdp::<MODEL_ID>::model().num_rewards()
+    }
+
+    fn reward(index: usize) -> f32 {
+        TabularMdp::<MODEL_ID>::model().rewards()[index]
+    }
+
+    fn transition_probability(state: usize, action: usize, next_state: usize, reward_index: usize) -> f32 {
+        TabularMdp::<MODEL_ID>::model()
+            .transitions(state, action)
+            .iter()
+            .find(|&&(s_p, r_index, _)| s_p == next_state && r_index == reward_index)
+            .map_or(0.0, |&(_, _, probability)| probability)
+    }
+
+    fn nonzero_transitions(state: usize, action: usize) -> Vec<(usize, usize, f32)> {
+        TabularMdp::<MODEL_ID>::model().transitions(state, action).to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envs::grid_world::GridWorld;
+
+    #[test]
+    fn answers_like_the_source_model_after_a_round_trip() {
+        let path = std::env::temp_dir().join(format!("tabular_mdp_{}.mdp", std::process::id()));
+        crate::models::model_file::save_model(&SparseTransitionModel::from_mdp_env::<GridWorld<3, 5>>(), &path).unwrap();
+        // Identifiant réservé à ce test : le registre est global
+        TabularMdp::<250>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        type Source = GridWorld<3, 5>;
+        assert_eq!(TabularMdp::<250>::num_states(), Source::num_states());
+        assert_eq!(TabularMdp::<250>::num_actions(), Source::num_actions());
+        for r_index in 0..Source::num_rewards() {
+            assert_eq!(TabularMdp::<250>::reward(r_index), Source::reward(r_index));
+        }
+        for s in 0..Source::num_states() {
+            for a in 0..Source::num_actions() {
+                for s_p in 0..Source::num_states() {
+                    for r_index in 0..Source::num_rewards() {
+                        assert_eq!(
+                            TabularMdp::<250>::transition_probability(s, a, s_p, r_index),
+                            Source::transition_probability(s, a, s_p, r_index),
+                            "p(s'={}, r={} | s={}, a={})", s_p, r_index, s, a,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+use std::fmt;
+use crate::contracts::mdp::Mdp;
+use crate::models::sparse_transition_model::SparseTransitionModel;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelValidationReport {
+    // (s, a, somme) dont les probabilités sur (s', r) ne somment ni à 1 ni à 0
+    pub invalid_probability_sums: Vec<(usize, usize, f32)>,
+    // (s, a) sans aucune transition : action interdite ou état terminal
+    pub zero_probability_pairs: Vec<(usize, usize)>,
+    // (s, a, s', r, p) avec p < 0
+    pub negative_probabilities: Vec<(usize, usize, usize, usize, f32)>,
+    // États vers lesquels aucun autre état ne mène
+    pub states_without_incoming: Vec<usize>,
+    // États qu'aucune suite de transitions de probabilité non nulle n'atteint depuis l'état de départ
+    // (None si aucun état de départ n'est donné, par exemple pour un fichier de modèle)
+    pub unreachable_states: Option<Vec<usize>>,
+    // États sans aucune transition sortante (toutes les actions à probabilité nulle)
+    pub terminal_states: Vec<usize>,
+    // États dont toutes les transitions sortantes reviennent sur eux-mêmes
+    pub absorbing_states: Vec<usize>,
+    pub unused_reward_indices: Vec<usize>,
+    // Actions de probabilité nulle dans tous les états
+    pub never_available_actions: Vec<usize>,
+}
+
+impl ModelValidationReport {
+    // Seules les sommes de probabilités incorrectes et les probabilités négatives rendent le modèle invalide,
+    // le reste du rapport est informatif
+    pub fn is_valid(&self) -> bool {
+        self.invalid_probability_sums.is_empty() && self.negative_probabilities.is_empty()
+    }
+}
+
+pub fn validate_mdp(mdp: &impl Mdp, tolerance: f32, start_state: Option<usize>) -> ModelValidationReport {
+    validate_model(&SparseTransitionModel::from_mdp(mdp), tolerance, start_state)
+}
+
+pub fn validate_model(model: &SparseTransitionModel, tolerance: f32, start_state: Option<usize>) -> ModelValidationReport {
+    let num_states = model.num_states();
+    let num_actions = model.num_actions();
+    let mut report = ModelValidationReport::default();
+
+    let mut has_incoming = vec![false; num_states];
+    let mut reward_used = vec![false; model.num_rewards()];
+    let mut action_used = vec![false; num_actions];
+
+    for s in 0..num_states {
+        let mut has_outgoing = false;
+        let mut only_self_loops = true;
+        for (a, action_used) in action_used.iter_mut().enumerate() {
+            let transitions = model.transitions(s, a);
+            if transitions.is_empty() {
+                report.zero_probability_pairs.push((s, a));
+                continue;
+            }
+            let mut total = 0.0;
+            for &(s_p, r_index, probability) in transitions {
+                if probability < 0.0 {
+                    report.negative_probabilities.push((s, a, s_p, r_index, probability));
+                    continue;
+                }
+                total += probability;
+                has_outgoing = true;
+                *action_used = true;
+                reward_used[r_index] = true;
+                if s_p != s {
+                    has_incoming[s_p] = true;
+                    only_self_loops = false;
+                }
+            }
+            if (total - 1.0).abs() > tolerance {
+                report.invalid_probability_sums.push((s, a, total));
+            }
+        }
+        if !has_outgoing {
+            report.terminal_states.push(s);
+        } else if only_self_loops {
+            report.absorbing_states.push(s);
+        }
+    }
+
+    report.states_without_incoming = (0..num_states).filter(|&s| !has_incoming[s]).collect();
+    report.unreachable_states = start_state.map(|start_state| {
+        let reachable = reachable_states(model, start_state);
+        (0..num_states).filter(|&s| !reachable[s]).collect()
+    });
+    report.unused_reward_indices = (0..model.num_rewards()).filter(|&r_index| !reward_used[r_index]).collect();
+    report.never_available_actions = (0..num_actions).filter(|&a| !action_used[a]).collect();
+    report
+}
+
+// Parcours en largeur depuis `start_state` sur les transitions de probabilité strictement positive
+fn reachable_states(model: &SparseTransitionModel, start_state: usize) -> Vec<bool> {
+    let mut reachable = vec![false; model.num_states()];
+    let mut queue = VecDeque::from([start_state]);
+    reachable[start_state] = true;
+    while let Some(s) = queue.pop_front() {
+        for a in 0..model.num_actions() {
+            for &(s_p, _, probability) in model.transitions(s, a) {
+                if probability > 0.0 && !reachable[s_p] {
+                    reachable[s_p] = true;
+                    queue.push_back(s_p);
+                }
+            }
+        }
+    }
+    reachable
+}
+
+impl fmt::Display for ModelValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Model is {}", if self.is_valid() { "valid" } else { "INVALID" })?;
+        writeln!(f, "{} (s, a) pairs with probabilities not summing to 1:", self.invalid_probability_sums.len())?;
+        for (s, a, total) in self.invalid_probability_sums.iter() {
+            writeln!(f, "  s={}, a={} : sum = {}", s, a, total)?;
+        }
+        writeln!(f, "{} negative probabilities:", self.negative_probabilities.len())?;
+        for (s, a, s_p, r_index, probability) in self.negative_probabilities.iter() {
+            writeln!(f, "  p(s'={}, r={} | s={}, a={}) = {}", s_p, r_index, s, a, probability)?;
+        }
+        writeln!(f, "{} (s, a) pairs without any transition", self.zero_probability_pairs.len())?;
+        writeln!(f, "States without incoming transitions: {:?}", self.states_without_incoming)?;
+        match &self.unreachable_states {
+            Some(unreachable_states) => writeln!(f, "Unreachable states from the start state: {:?}", unreachable_states)?,
+            None => writeln!(f, "Unreachable states: not checked (no start state)")?,
+        }
+        writeln!(f, "Terminal states: {:?}", self.terminal_states)?;
+        writeln!(f, "Absorbing states: {:?}", self.absorbing_states)?;
+        writeln!(f, "Unused reward indices: {:?}", self.unused_reward_indices)?;
+        write!(f, "Never available actions: {:?}", self.never_available_actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::mdp::StaticMdp;
+    use crate::envs::grid_world::GridWorld;
+    use crate::envs::line_world::LineWorld;
+
+    #[test]
+    fn grid_world_probabilities_do_not_sum_to_one() {
+        let report = validate_mdp(&StaticMdp::<GridWorld<4, 4>>::new(), 1e-4, Some(0));
+        assert!(!report.is_valid());
+        // Chaque déplacement possible depuis un état non terminal cumule ses trois probabilités par récompense,
+        // par exemple 0.5 + 0.7 + 0.9 vers le haut
+        // 48 déplacements dans la grille, moins les 2 + 2 depuis les états terminaux 0 et 15
+        assert_eq!(report.invalid_probability_sums.len(), 44);
+        assert!(report.invalid_probability_sums.iter().all(|&(_, _, sum)| sum > 1.0));
+        let (_, _, up_sum) = report.invalid_probability_sums.iter().find(|&&(s, a, _)| (s, a) == (5, 0)).unwrap();
+        assert!((up_sum - 2.1).abs() < 1e-5);
+        assert_eq!(report.terminal_states, vec![0, 15]);
+    }
+
+    #[test]
+    fn line_world_validates_clean() {
+        let report = validate_mdp(&StaticMdp::<LineWorld<5>>::new(), 1e-4, Some(2));
+        assert!(report.is_valid(), "{}", report);
+        assert!(report.negative_probabilities.is_empty());
+        assert_eq!(report.terminal_states, vec![0, 4]);
+        assert_eq!(report.unreachable_states, Some(vec![]));
+        assert!(report.unused_reward_indices.is_empty());
+    }
+
+    #[test]
+    fn states_looping_on_each_other_are_unreachable() {
+        // 0 -> 1 (terminal), et 2 <-> 3 qui ne mènent que l'un à l'autre : 2 et 3 ont des transitions entrantes
+        // mais ne sont pas atteignables depuis 0
+        let model = SparseTransitionModel::from_parts(4, 1, vec![0.0], vec![0, 1, 1, 2, 3], vec![(1, 0, 1.0), (3, 0, 1.0), (2, 0, 1.0)]);
+        let report = validate_model(&model, 1e-4, Some(0));
+        assert!(report.is_valid());
+        assert_eq!(report.states_without_incoming, vec![0]);
+        assert_eq!(report.unreachable_states, Some(vec![2, 3]));
+        assert_eq!(validate_model(&model, 1e-4, None).unreachable_states, None);
+    }
+}